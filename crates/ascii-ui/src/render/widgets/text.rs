@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::{theme::UiTheme, widgets::text::Text};
 use glyph_render::{
-    glyph_render_plugin::{GlyphTexture, SolidColor},
+    glyph_render_plugin::{GlyphTexture, GlyphTextureSource, SolidColor},
     glyph_sprite::GlyphSprite,
 };
 use spatial_grid::depth::Depth;
@@ -18,7 +18,13 @@ pub(crate) fn text_render(
         if text.text.len() > 0 {
             entity_commands.insert((
                 GlyphSprite {
-                    texture: glyph_textures.add(GlyphTexture::from(vec![text.text.clone()])),
+                    texture: glyph_textures.add(if text.markup {
+                        GlyphTexture::from(GlyphTextureSource::from_markup(&text.text, |style| {
+                            theme.markup_color(style)
+                        }))
+                    } else {
+                        GlyphTexture::from(vec![text.text.clone()])
+                    }),
                     offset: IVec2::ZERO,
                 },
                 Depth(0.0),
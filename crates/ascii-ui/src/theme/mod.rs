@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use glyph_render::glyph_render_plugin::markup_color;
+use text_util::text_markup::MarkupStyle;
 
 mod plugin;
 
@@ -41,6 +43,23 @@ pub struct UiTheme {
     pub text_secondary: TextStyle,
 }
 
+impl UiTheme {
+    /// Resolves theme colour names and `#rrggbb` colours, bold text without a colour is heavy.
+    pub fn markup_color(&self, style: &MarkupStyle) -> Option<Color> {
+        let style = match style.color.as_deref() {
+            Some("subtle") => &self.text_subtle,
+            Some("regular") => &self.text_regular,
+            Some("heavy") => &self.text_heavy,
+            Some("primary") => &self.text_primary,
+            Some("secondary") => &self.text_secondary,
+            Some(_) => return markup_color(style),
+            None if style.bold => &self.text_heavy,
+            None => return None,
+        };
+        Some(style.color)
+    }
+}
+
 impl Default for UiTheme {
     fn default() -> Self {
         Self {
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use text_util::{text_markup::strip_markup, text_width::line_width};
 
 use crate::{
     attachments::Padding,
@@ -16,6 +17,8 @@ use crate::{
 pub struct Text {
     pub text: String,
     pub style: TextTheme,
+    /// Parse `text` as markup, see `text_util::text_markup`.
+    pub markup: bool,
 }
 #[derive(Debug, Default)]
 
@@ -35,8 +38,13 @@ impl WidgetLayoutLogic for TextLogic {
         let padding = world.get::<Padding>(entity).cloned().unwrap_or_default();
         let padding_total = padding.total();
         // let padding_offset = IVec2::new(padding.0.left, padding.0.top);
+        let width = if text.markup {
+            line_width(&strip_markup(&text.text))
+        } else {
+            line_width(&text.text)
+        };
         return UVec2 {
-            x: text.text.len() as u32,
+            x: width as u32,
             y: 1,
         } + padding_total;
     }
@@ -57,6 +65,7 @@ impl<T: Bundle> TextBundle<T> {
             text: Text {
                 text,
                 style: Default::default(),
+                markup: false,
             },
             attachments,
         }
@@ -71,12 +80,34 @@ impl Text {
         Self::build_styled(text, Default::default())
     }
 
+    /// Text with inline markup such as `[color=primary]Hello[/] [b]world[/]`.
+    pub fn build_markup<'a>(text: impl Into<String> + 'a) -> WidgetBuilder<'a> {
+        let text = text.into();
+        WidgetBuilder::new(move |commands| {
+            commands
+                .spawn((
+                    Self {
+                        text,
+                        style: Default::default(),
+                        markup: true,
+                    },
+                    RenderBundle::default(),
+                    WidgetLayout::new::<TextLogic>(),
+                ))
+                .id()
+        })
+    }
+
     pub fn build_styled<'a>(text: impl Into<String> + 'a, style: TextTheme) -> WidgetBuilder<'a> {
         let text = text.into();
         WidgetBuilder::new(move |commands| {
             commands
                 .spawn((
-                    Self { text, style },
+                    Self {
+                        text,
+                        style,
+                        markup: false,
+                    },
                     RenderBundle::default(),
                     WidgetLayout::new::<TextLogic>(),
                 ))
@@ -4,6 +4,7 @@ use bevy::{
 };
 
 use std::sync::Arc;
+use text_util::text_art::DEFAULT_CHARSET;
 
 mod builder;
 mod plugin;
@@ -11,8 +12,7 @@ mod plugin;
 pub struct CharacterSet(pub HashSet<char>);
 impl Default for CharacterSet {
     fn default() -> Self {
-        const CHARSET: &str = "!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_´`abcdefghijklmnopqrstuvwxyz{|}~";
-        Self(CHARSET.chars().collect())
+        Self(DEFAULT_CHARSET.chars().collect())
     }
 }
 
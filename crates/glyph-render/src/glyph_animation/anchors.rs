@@ -0,0 +1,59 @@
+use bevy::{prelude::*, utils::HashMap};
+use spatial_grid::position::Position;
+
+use super::{GlyphAnimation, GlyphAnimationSource};
+use crate::glyph_render_plugin::GlyphSpriteMirrored;
+
+/// Named anchor points of the current animation frame, relative to the entity's `Position`.
+#[derive(Debug, Component, Clone, Default, Deref)]
+pub struct GlyphAnimationAnchors(HashMap<String, IVec2>);
+
+/// Keeps this entity's `Position` on an anchor of another entity's animation.
+#[derive(Debug, Component, Clone)]
+pub struct AnchorAttachment {
+    pub target: Entity,
+    pub anchor: String,
+    pub offset: IVec2,
+}
+
+pub fn update_animation_anchors(
+    mut q_anchors: Query<(
+        &GlyphAnimation,
+        Has<GlyphSpriteMirrored>,
+        &mut GlyphAnimationAnchors,
+    )>,
+    glyph_animations: Res<Assets<GlyphAnimationSource>>,
+) {
+    for (animation, mirrored, mut anchors) in q_anchors.iter_mut() {
+        let Some(frame) = glyph_animations
+            .get(&animation.source)
+            .and_then(|source| source.frame(animation.frame, mirrored))
+        else {
+            continue;
+        };
+
+        anchors.0.clear();
+        anchors.0.extend(
+            frame
+                .anchors
+                .iter()
+                .map(|(name, anchor)| (name.clone(), *anchor + frame.offset)),
+        );
+    }
+}
+
+pub fn update_anchor_attachments(
+    mut q_attachments: Query<(&AnchorAttachment, &mut Position)>,
+    q_targets: Query<(&Position, &GlyphAnimationAnchors), Without<AnchorAttachment>>,
+) {
+    for (attachment, mut position) in q_attachments.iter_mut() {
+        let Ok((target_position, anchors)) = q_targets.get(attachment.target) else {
+            continue;
+        };
+        let Some(anchor) = anchors.get(&attachment.anchor) else {
+            continue;
+        };
+
+        **position = **target_position + *anchor + attachment.offset;
+    }
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+use super::{GlyphAnimation, GlyphAnimationSource};
+use crate::glyph_render_plugin::GlyphSpriteMirrored;
+
+/// Hit and hurt boxes of the current animation frame, relative to the entity's `Position`.
+#[derive(Debug, Component, Clone, Default, PartialEq)]
+pub struct GlyphAnimationHitboxes {
+    pub hitboxes: Vec<IRect>,
+    pub hurtboxes: Vec<IRect>,
+}
+
+type HitboxesQueryData<'a> = (
+    &'a GlyphAnimation,
+    Has<GlyphSpriteMirrored>,
+    &'a mut GlyphAnimationHitboxes,
+);
+
+pub fn update_animation_hitboxes(
+    mut q_hitboxes: Query<HitboxesQueryData>,
+    glyph_animations: Res<Assets<GlyphAnimationSource>>,
+) {
+    for (animation, mirrored, mut hitboxes) in q_hitboxes.iter_mut() {
+        let Some(frame) = glyph_animations
+            .get(&animation.source)
+            .and_then(|source| source.frame(animation.frame, mirrored))
+        else {
+            continue;
+        };
+
+        let translate = |rect: &IRect| IRect {
+            min: rect.min + frame.offset,
+            max: rect.max + frame.offset,
+        };
+        // Only written when the boxes differ so `Changed` marks actual frame changes.
+        hitboxes.set_if_neq(GlyphAnimationHitboxes {
+            hitboxes: frame.hitboxes.iter().map(translate).collect(),
+            hurtboxes: frame.hurtboxes.iter().map(translate).collect(),
+        });
+    }
+}
@@ -1,10 +1,14 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use text_util::text_width::slice_cells;
 
 #[derive(serde::Deserialize, Asset, TypePath)]
 pub(crate) struct GlyphAnimationMeta {
     pub(crate) name: String,
     pub(crate) size: (u32, u32),
 
+    #[serde(default)]
+    pub(crate) mirror_map: Option<String>,
+
     // #[serde(default)]
     // pub(crate) default_name: Option<String>,
     pub(crate) frames: Vec<(FrameMeta, MirroredFrame)>,
@@ -33,6 +37,59 @@ pub(crate) struct FrameMeta {
 
     #[serde(default)]
     pub(crate) frame_count: CountDirection,
+
+    #[serde(default)]
+    pub(crate) anchors: HashMap<String, AnchorMeta>,
+
+    #[serde(default)]
+    pub(crate) hitboxes: BoxesMeta,
+
+    #[serde(default)]
+    pub(crate) hurtboxes: BoxesMeta,
+}
+
+// Anchor cells are given from the top left of the frame, either once for every
+// frame in the sequence or as a list with one entry per frame.
+#[derive(serde::Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub(crate) enum AnchorMeta {
+    Single((i32, i32)),
+    PerFrame(Vec<(i32, i32)>),
+}
+
+// Boxes use the same top left frame cells as anchors.
+#[derive(serde::Deserialize, Clone, Debug)]
+pub(crate) struct BoxMeta {
+    pub(crate) start: (i32, i32),
+    pub(crate) size: (u32, u32),
+}
+
+#[derive(serde::Deserialize, Clone, Debug, Default)]
+#[serde(untagged)]
+pub(crate) enum BoxesMeta {
+    #[default]
+    None,
+    Single(Vec<BoxMeta>),
+    PerFrame(Vec<Vec<BoxMeta>>),
+}
+
+impl BoxesMeta {
+    pub(crate) fn get(&self, index: usize) -> &[BoxMeta] {
+        match self {
+            Self::None => &[],
+            Self::Single(boxes) => boxes,
+            Self::PerFrame(boxes) => boxes.get(index).map(Vec::as_slice).unwrap_or_default(),
+        }
+    }
+}
+
+impl AnchorMeta {
+    pub(crate) fn get(&self, index: usize) -> Option<IVec2> {
+        match self {
+            Self::Single(anchor) => Some((*anchor).into()),
+            Self::PerFrame(anchors) => anchors.get(index).map(|&anchor| anchor.into()),
+        }
+    }
 }
 
 pub(crate) fn create_data(
@@ -50,20 +107,19 @@ pub(crate) fn create_data(
 
     let mut frame_data = vec![String::new(); frame_size.y as usize];
     for (dst_y, src_y) in (start_y..start_y + frame.size.1).enumerate() {
-        let line = &data[src_y as usize];
+        let line = data
+            .get(src_y as usize)
+            .map(String::as_str)
+            .unwrap_or_default();
 
-        let src_start_x = start_x as usize;
-        let src_data_width = (frame.size.0 as usize).min(line.chars().count() - src_start_x);
-        // let src_end_x = src_start_x + src_data_width;
+        let offset_x = frame.offset.0.max(0) as usize;
+        let src_data_width =
+            (frame.size.0 as usize).min((frame_size.x as usize).saturating_sub(offset_x));
 
-        let prefix = " ".repeat(frame.offset.0 as usize);
-        let suffix = " ".repeat(frame_size.x as usize - frame.offset.0 as usize - src_data_width);
+        let prefix = " ".repeat(offset_x);
+        let suffix = " ".repeat(frame_size.x as usize - offset_x - src_data_width);
 
-        let line_data = line
-            .chars()
-            .skip(src_start_x)
-            .take(src_data_width)
-            .collect::<String>();
+        let line_data = slice_cells(line, start_x as usize, src_data_width);
         frame_data[dst_y] = prefix + &line_data + &suffix;
     }
 
@@ -5,33 +5,24 @@ use bevy::{
 };
 
 use self::meta::{
-    create_data, CountDirection, FrameIndex, FrameMeta, GlyphAnimationMeta, MirroredFrame,
+    create_data, BoxMeta, CountDirection, FrameIndex, FrameMeta, GlyphAnimationMeta, MirroredFrame,
 };
 use super::{GlyphAnimationFrame, GlyphAnimationSource};
-use text_util::text_mirror::mirror_lines;
+use crate::mirror_map::MirrorMap;
+use std::path::Path;
+use text_util::{compression::Compression, text_art::parse_art_lines};
 
 pub mod meta;
 
 #[derive(Default)]
 pub struct GlyphAnimationAssetLoader {}
-impl GlyphAnimationAssetLoader {
-    fn parse_bytes(bytes: Vec<u8>) -> Vec<String> {
-        bytes
-            .split(|&b| b == b'\n')
-            .map(|line| {
-                String::from_utf8(line.strip_suffix(b"\r").unwrap_or(line).to_vec()).unwrap()
-            })
-            .collect::<Vec<_>>()
-    }
-}
-
 impl AssetLoader for GlyphAnimationAssetLoader {
     type Asset = GlyphAnimationSource;
     type Error = anyhow::Error;
     type Settings = ();
 
     fn extensions(&self) -> &[&str] {
-        &["anim.ron"]
+        &["anim.ron", "anim.ron.gz", "anim.ron.zst"]
     }
 
     fn load(
@@ -43,6 +34,7 @@ impl AssetLoader for GlyphAnimationAssetLoader {
         Box::pin(async move {
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
+            let bytes = Compression::from_path(load_context.path()).decompress(bytes)?;
             let meta = {
                 let mut meta = ron::de::from_bytes::<GlyphAnimationMeta>(&bytes)?;
                 for frame in meta.frames.iter_mut() {
@@ -77,10 +69,15 @@ impl AssetLoader for GlyphAnimationAssetLoader {
             for asset_path in unique_assets.iter() {
                 source_file_data.insert(
                     asset_path,
-                    Self::parse_bytes(load_context.read_asset_bytes(asset_path).await.unwrap()),
+                    parse_art_lines(
+                        &Compression::from_path(Path::new(asset_path))
+                            .decompress(load_context.read_asset_bytes(asset_path).await?)?,
+                    )?,
                 );
             }
 
+            let mirror_map = MirrorMap::load(load_context, meta.mirror_map.as_ref()).await?;
+
             let mut frame_data: Vec<Vec<String>> = Vec::with_capacity(meta.frames.len());
             let mut mirrored_frame_data: Vec<Vec<String>> = Vec::new();
             build_frames(
@@ -101,27 +98,35 @@ impl AssetLoader for GlyphAnimationAssetLoader {
             let mut frames: Vec<(GlyphAnimationFrame, Option<GlyphAnimationFrame>)> =
                 Vec::with_capacity(frame_data.len());
             let mut mirrored_iter = mirrored_frame_data.into_iter();
-            for (data, meta) in frame_data.into_iter().zip(
-                meta.frames
-                    .iter()
-                    .flat_map(|meta| vec![meta.clone(); meta.0.frame_count.count() as usize]),
-            ) {
+            let frame_size: UVec2 = meta.size.into();
+            for (data, (meta, index)) in
+                frame_data
+                    .into_iter()
+                    .zip(meta.frames.iter().flat_map(|meta| {
+                        (0..meta.0.frame_count.count() as usize).map(move |index| (meta, index))
+                    }))
+            {
+                let offset: IVec2 = meta.0.offset.into();
                 let mirrored = match &meta.1 {
-                    MirroredFrame::Auto(mirror_offset_x, mirror_offset_y) => {
-                        Some(GlyphAnimationFrame::new(
-                            mirror_lines(&data),
-                            Into::<IVec2>::into(meta.0.offset)
-                                + Into::<IVec2>::into((*mirror_offset_x, *mirror_offset_y)),
-                        ))
-                    }
-                    MirroredFrame::Override(meta) => Some(GlyphAnimationFrame::new(
-                        mirrored_iter.next().expect("Missing mirrored frame!"),
-                        meta.offset.into(),
-                    )),
+                    MirroredFrame::Auto(mirror_offset_x, mirror_offset_y) => Some(
+                        GlyphAnimationFrame::new(
+                            mirror_map.mirror_lines(&data),
+                            offset + Into::<IVec2>::into((*mirror_offset_x, *mirror_offset_y)),
+                        )
+                        .with_markers(&meta.0, index, frame_size, true),
+                    ),
+                    MirroredFrame::Override(meta) => Some(
+                        GlyphAnimationFrame::new(
+                            mirrored_iter.next().expect("Missing mirrored frame!"),
+                            meta.offset.into(),
+                        )
+                        .with_markers(meta, index, frame_size, false),
+                    ),
                     MirroredFrame::None => None,
                 };
                 frames.push((
-                    GlyphAnimationFrame::new(data, meta.0.offset.into()),
+                    GlyphAnimationFrame::new(data, offset)
+                        .with_markers(&meta.0, index, frame_size, false),
                     mirrored,
                 ))
             }
@@ -134,6 +139,43 @@ impl AssetLoader for GlyphAnimationAssetLoader {
     }
 }
 
+impl GlyphAnimationFrame {
+    // Converts markers from top left frame cells to cells relative to the frame origin.
+    fn with_markers(
+        mut self,
+        frame: &FrameMeta,
+        index: usize,
+        size: UVec2,
+        mirrored: bool,
+    ) -> Self {
+        let size = size.as_ivec2();
+        let flip_x = |x: i32, width: i32| if mirrored { size.x - x - width } else { x };
+
+        self.anchors = frame
+            .anchors
+            .iter()
+            .filter_map(|(name, anchor)| {
+                let IVec2 { x, y } = anchor.get(index)?;
+                Some((name.clone(), IVec2::new(flip_x(x, 1), size.y - 1 - y)))
+            })
+            .collect();
+
+        let to_rect = |BoxMeta {
+                           start,
+                           size: box_size,
+                       }: &BoxMeta| {
+            let min = IVec2::new(
+                flip_x(start.0, box_size.0 as i32),
+                size.y - start.1 - box_size.1 as i32,
+            );
+            IRect::from_corners(min, min + UVec2::from(*box_size).as_ivec2())
+        };
+        self.hitboxes = frame.hitboxes.get(index).iter().map(to_rect).collect();
+        self.hurtboxes = frame.hurtboxes.get(index).iter().map(to_rect).collect();
+        self
+    }
+}
+
 fn build_frames(
     size: UVec2,
     meta: &mut dyn Iterator<Item = &FrameMeta>,
@@ -1,9 +1,14 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 
-use self::loader::GlyphAnimationAssetLoader;
+use self::{
+    anchors::GlyphAnimationAnchors, hitboxes::GlyphAnimationHitboxes,
+    loader::GlyphAnimationAssetLoader,
+};
 use crate::glyph_render_plugin::GlyphTextureSource;
 use std::sync::Arc;
 
+pub mod anchors;
+pub mod hitboxes;
 mod loader;
 pub mod player;
 
@@ -14,10 +19,24 @@ pub struct GlyphAnimationSource {
     pub(crate) frames: Vec<(GlyphAnimationFrame, Option<GlyphAnimationFrame>)>,
 }
 
+impl GlyphAnimationSource {
+    pub(crate) fn frame(&self, frame: u32, mirrored: bool) -> Option<&GlyphAnimationFrame> {
+        let (data, mirrored_data) = self.frames.get(frame as usize)?;
+        if mirrored {
+            Some(mirrored_data.as_ref().unwrap_or(data))
+        } else {
+            Some(data)
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct GlyphAnimationFrame {
     pub(crate) source: Arc<GlyphTextureSource>,
     pub(crate) offset: IVec2,
+    pub(crate) anchors: HashMap<String, IVec2>,
+    pub(crate) hitboxes: Vec<IRect>,
+    pub(crate) hurtboxes: Vec<IRect>,
 }
 
 impl GlyphAnimationFrame {
@@ -25,11 +44,15 @@ impl GlyphAnimationFrame {
         Self {
             source: Arc::new((&data).into()),
             offset,
+            anchors: HashMap::new(),
+            hitboxes: Vec::new(),
+            hurtboxes: Vec::new(),
         }
     }
 }
 
-#[derive(Component, Clone)]
+#[derive(Debug, Component, Clone)]
+#[require(GlyphAnimationAnchors, GlyphAnimationHitboxes)]
 pub struct GlyphAnimation {
     pub source: Handle<GlyphAnimationSource>,
     pub frame: u32,
@@ -0,0 +1,247 @@
+use bevy::prelude::*;
+use std::sync::Arc;
+
+use super::{
+    player::{
+        advance_graph_animation, traverse_graph, GlyphAnimationGraphCurrent,
+        GlyphAnimationGraphSettings, GlyphAnimationGraphTarget,
+    },
+    GlyphAnimationGraph, GlyphAnimationGraphSource,
+};
+use crate::{
+    glyph_animation::{GlyphAnimation, GlyphAnimationSource},
+    glyph_render_plugin::{GlyphSpriteMirrored, GlyphTextureSource},
+};
+
+#[derive(Debug, Component, Clone, Default)]
+pub struct GlyphAnimationLayers {
+    pub layers: Vec<GlyphAnimationLayer>,
+}
+
+impl GlyphAnimationLayers {
+    pub fn get(&self, name: &str) -> Option<&GlyphAnimationLayer> {
+        self.layers.iter().find(|layer| layer.name == name)
+    }
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut GlyphAnimationLayer> {
+        self.layers.iter_mut().find(|layer| layer.name == name)
+    }
+}
+
+/// An animation graph drawn on top of the entity's base animation.
+///
+/// `offset` and `mask` are in cells relative to the base animation origin.
+/// Spaces in the layer are transparent, and cells outside `mask` are ignored.
+#[derive(Debug, Clone)]
+pub struct GlyphAnimationLayer {
+    pub name: String,
+    pub target: GlyphAnimationGraphTarget,
+    pub settings: GlyphAnimationGraphSettings,
+    pub offset: IVec2,
+    pub mask: Option<IRect>,
+    pub(crate) graph: GlyphAnimationGraph,
+    pub(crate) current: GlyphAnimationGraphCurrent,
+    pub(crate) animation: Option<GlyphAnimation>,
+}
+
+impl GlyphAnimationLayer {
+    pub fn from_source(name: impl Into<String>, source: Handle<GlyphAnimationGraphSource>) -> Self {
+        Self {
+            name: name.into(),
+            target: Default::default(),
+            settings: Default::default(),
+            offset: IVec2::ZERO,
+            mask: None,
+            graph: GlyphAnimationGraph { source },
+            current: Default::default(),
+            animation: None,
+        }
+    }
+}
+
+#[derive(Debug, Component, Clone)]
+pub struct GlyphAnimationComposite {
+    pub(crate) source: Arc<GlyphTextureSource>,
+    pub(crate) offset: IVec2,
+    key: Vec<CompositeKey>,
+    mirrored: bool,
+}
+
+/// Animation frame and resolved placement of one composited source.
+#[derive(Debug, Clone, PartialEq)]
+struct CompositeKey {
+    animation: AssetId<GlyphAnimationSource>,
+    frame: u32,
+    offset: IVec2,
+    mask: Option<IRect>,
+}
+
+type CompositeSource<'a> = (&'a Arc<GlyphTextureSource>, IVec2, Option<IRect>);
+
+type StaleCompositeFilter = (
+    With<GlyphAnimationComposite>,
+    Or<(Without<GlyphAnimationLayers>, Without<GlyphAnimation>)>,
+);
+
+type LayersQueryData<'a> = (
+    Entity,
+    &'a GlyphAnimation,
+    &'a GlyphAnimationLayers,
+    Has<GlyphSpriteMirrored>,
+    Option<&'a GlyphAnimationComposite>,
+);
+
+pub(crate) fn animation_layers_player(
+    mut q_layers: Query<&mut GlyphAnimationLayers>,
+    time: Res<Time>,
+    glyph_animations: Res<Assets<GlyphAnimationSource>>,
+    glyph_animation_graphs: Res<Assets<GlyphAnimationGraphSource>>,
+) {
+    for mut layers in q_layers.iter_mut() {
+        for layer in layers.layers.iter_mut() {
+            traverse_graph(
+                &layer.graph,
+                &mut layer.current,
+                &layer.target,
+                &glyph_animation_graphs,
+            );
+            if let Some(animation) = advance_graph_animation(
+                &layer.graph,
+                &mut layer.current,
+                layer.animation.as_mut(),
+                &layer.settings,
+                time.delta_secs(),
+                &glyph_animations,
+                &glyph_animation_graphs,
+            ) {
+                layer.animation = Some(animation);
+            }
+        }
+    }
+}
+
+pub(crate) fn composite_animation_layers(
+    mut commands: Commands,
+    q_layers: Query<LayersQueryData>,
+    glyph_animations: Res<Assets<GlyphAnimationSource>>,
+) {
+    for (entity, base, layers, mirrored, composite) in q_layers.iter() {
+        let Some(base_source) = glyph_animations.get(&base.source) else {
+            continue;
+        };
+        let Some(base_frame) = base_source.frame(base.frame, mirrored) else {
+            continue;
+        };
+
+        let mut key = vec![CompositeKey {
+            animation: base.source.id(),
+            frame: base.frame,
+            offset: IVec2::ZERO,
+            mask: None,
+        }];
+        let mut sources = vec![(&base_frame.source, base_frame.offset, None)];
+        for layer in layers.layers.iter() {
+            let Some(animation) = &layer.animation else {
+                continue;
+            };
+            let Some(frame) = glyph_animations
+                .get(&animation.source)
+                .and_then(|source| source.frame(animation.frame, mirrored))
+            else {
+                continue;
+            };
+
+            let size = IVec2::new(frame.source.width as i32, frame.source.height as i32);
+            let (offset, mask) = if mirrored {
+                let base_width = base_source.size.x as i32;
+                let offset = IVec2::new(base_width - layer.offset.x - size.x, layer.offset.y);
+                let mask = layer.mask.map(|mask| {
+                    IRect::new(
+                        base_width - mask.max.x,
+                        mask.min.y,
+                        base_width - mask.min.x,
+                        mask.max.y,
+                    )
+                });
+                (offset, mask)
+            } else {
+                (layer.offset, layer.mask)
+            };
+
+            key.push(CompositeKey {
+                animation: animation.source.id(),
+                frame: animation.frame,
+                offset,
+                mask,
+            });
+            sources.push((&frame.source, offset + frame.offset, mask));
+        }
+
+        if composite.is_some_and(|composite| composite.key == key && composite.mirrored == mirrored)
+        {
+            continue;
+        }
+
+        let (source, offset) = composite_sources(&sources);
+        commands.entity(entity).insert(GlyphAnimationComposite {
+            source: Arc::new(source),
+            offset,
+            key,
+            mirrored,
+        });
+    }
+}
+
+pub(crate) fn remove_animation_composites(
+    mut commands: Commands,
+    q_composites: Query<Entity, StaleCompositeFilter>,
+) {
+    for entity in q_composites.iter() {
+        commands.entity(entity).remove::<GlyphAnimationComposite>();
+    }
+}
+
+// Offsets are the bottom left cell of each source, rows are stored top to bottom.
+fn composite_sources(sources: &[CompositeSource]) -> (GlyphTextureSource, IVec2) {
+    let bounds = sources
+        .iter()
+        .map(|(source, offset, mask)| {
+            let rect = IRect::from_corners(
+                *offset,
+                *offset + IVec2::new(source.width as i32, source.height as i32),
+            );
+            mask.map(|mask| rect.intersect(mask)).unwrap_or(rect)
+        })
+        .filter(|rect| !rect.is_empty())
+        .reduce(|a, b| a.union(b))
+        .unwrap_or_default();
+
+    let width = bounds.width() as usize;
+    let height = bounds.height() as usize;
+    let mut data = vec![' '; width * height].into_boxed_slice();
+
+    for (source, offset, mask) in sources {
+        for (index, c) in source.data.iter().copied().enumerate() {
+            if c == ' ' {
+                continue;
+            }
+            let cell = *offset
+                + IVec2::new(
+                    (index % source.width) as i32,
+                    (source.height - index / source.width - 1) as i32,
+                );
+            if mask.is_some_and(|mask| !contains_cell(mask, cell)) || !contains_cell(bounds, cell) {
+                continue;
+            }
+
+            let local = cell - bounds.min;
+            let row = height - local.y as usize - 1;
+            data[local.x as usize + row * width] = c;
+        }
+    }
+
+    (GlyphTextureSource::new(width, height, data), bounds.min)
+}
+
+fn contains_cell(rect: IRect, cell: IVec2) -> bool {
+    cell.cmpge(rect.min).all() && cell.cmplt(rect.max).all()
+}
@@ -1,20 +1,23 @@
 use bevy::{
     asset::{io::Reader, AssetLoader, LoadContext},
     prelude::*,
-    utils::{
-        hashbrown::{HashMap, HashSet},
-        ConditionalSendFuture,
-    },
+    utils::{hashbrown::HashMap, ConditionalSendFuture},
 };
 
 use crate::glyph_animation::GlyphAnimationSource;
 use anyhow::Context;
 use serde::Deserialize;
-use std::collections::VecDeque;
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
 
 pub mod bundle;
+pub mod layers;
 pub mod player;
 pub mod plugin;
+#[cfg(feature = "hot_reload")]
+mod reload;
 
 #[derive(Default)]
 pub(crate) struct GlyphAnimationGraphAssetLoader {}
@@ -40,7 +43,7 @@ impl AssetLoader for GlyphAnimationGraphAssetLoader {
             let meta = ron::de::from_bytes::<GlyphAnimationGraphMeta>(&bytes)?;
 
             let mut states = Vec::with_capacity(meta.states.len());
-            let mut transitions = vec![vec![]; meta.transitions.len()];
+            let mut transitions = vec![vec![]; meta.states.len()];
 
             let mut state_names: HashMap<String, usize> = HashMap::with_capacity(meta.states.len());
 
@@ -55,6 +58,7 @@ impl AssetLoader for GlyphAnimationGraphAssetLoader {
                 states.push(GlyphAnimationGraphState {
                     // name: state.name,
                     animation: load_context.load(state.animation),
+                    pass_through: state.pass_through,
                 });
             }
 
@@ -69,6 +73,7 @@ impl AssetLoader for GlyphAnimationGraphAssetLoader {
                 ))?;
                 transitions[*from].push(GlyphAnimationGraphTransition {
                     to: *to,
+                    cost: transition.cost,
                     animation: transition
                         .animation
                         .map(|animation| load_context.load(animation)),
@@ -85,37 +90,59 @@ impl AssetLoader for GlyphAnimationGraphAssetLoader {
 }
 
 pub(crate) struct GlyphAnimationTransition {
-    pub(crate) transitions: Option<Vec<Handle<GlyphAnimationSource>>>,
+    pub(crate) transitions: Option<VecDeque<Handle<GlyphAnimationSource>>>,
 }
 
 impl GlyphAnimationGraphSource {
+    // Cheapest path by (cost, hops), ties go to the first declared transition.
+    // States with `pass_through: false` can only start or end a path.
     pub(crate) fn traverse(&self, src: usize, dest: usize) -> GlyphAnimationTransition {
-        let mut visited: HashSet<usize> = HashSet::new();
-        let mut queue = VecDeque::<(usize, Vec<Handle<GlyphAnimationSource>>)>::new();
+        let mut best: Vec<Option<(u32, u32)>> = vec![None; self.states.len()];
+        let mut previous: Vec<Option<(usize, usize)>> = vec![None; self.states.len()];
+        let mut queue = BinaryHeap::<Reverse<(u32, u32, usize)>>::new();
 
-        queue.push_back((src, vec![]));
-        while let Some((node, path)) = queue.pop_front() {
+        best[src] = Some((0, 0));
+        queue.push(Reverse((0, 0, src)));
+        while let Some(Reverse((cost, hops, node))) = queue.pop() {
             if node == dest {
                 return GlyphAnimationTransition {
-                    transitions: Some(path),
+                    transitions: Some(self.collect_path(&previous, dest)),
                 };
             }
-            visited.insert(node);
-            for transition in &self.transitions[node] {
-                let child = transition.to;
-                if !visited.contains(&transition.to) {
-                    let mut path = path.clone();
-                    if let Some(animation) = &transition.animation {
-                        path.push(animation.clone());
-                    }
-
-                    queue.push_back((child, path));
+            if best[node].is_some_and(|best| best < (cost, hops)) {
+                continue;
+            }
+            if node != src && !self.states[node].pass_through {
+                continue;
+            }
+            for (index, transition) in self.transitions[node].iter().enumerate() {
+                let next = (cost.saturating_add(transition.cost), hops + 1);
+                if best[transition.to].is_none_or(|best| next < best) {
+                    best[transition.to] = Some(next);
+                    previous[transition.to] = Some((node, index));
+                    queue.push(Reverse((next.0, next.1, transition.to)));
                 }
             }
         }
 
         GlyphAnimationTransition { transitions: None }
     }
+
+    fn collect_path(
+        &self,
+        previous: &[Option<(usize, usize)>],
+        dest: usize,
+    ) -> VecDeque<Handle<GlyphAnimationSource>> {
+        let mut path = VecDeque::new();
+        let mut node = dest;
+        while let Some((from, index)) = previous[node] {
+            if let Some(animation) = &self.transitions[from][index].animation {
+                path.push_front(animation.clone());
+            }
+            node = from;
+        }
+        path
+    }
 }
 
 #[derive(Deserialize)]
@@ -128,6 +155,12 @@ struct GlyphAnimationGraphMeta {
 struct GlyphAnimationGraphStateMeta {
     name: String,
     animation: String,
+    #[serde(default = "default_pass_through")]
+    pass_through: bool,
+}
+
+fn default_pass_through() -> bool {
+    true
 }
 
 #[derive(Deserialize)]
@@ -136,6 +169,12 @@ struct GlyphAnimationGraphTransitionMeta {
     to: String,
     #[serde(default, deserialize_with = "wrap_some")]
     animation: Option<String>,
+    #[serde(default = "default_transition_cost")]
+    cost: u32,
+}
+
+fn default_transition_cost() -> u32 {
+    1
 }
 
 fn wrap_some<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
@@ -155,11 +194,13 @@ pub struct GlyphAnimationGraphSource {
 
 struct GlyphAnimationGraphState {
     animation: Handle<GlyphAnimationSource>,
+    pass_through: bool,
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct GlyphAnimationGraphTransition {
     to: usize,
+    cost: u32,
     animation: Option<Handle<GlyphAnimationSource>>,
 }
 
@@ -173,3 +214,75 @@ pub struct GlyphAnimationGraph {
 //         Self { source: graph }
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Transition `i` plays the animation `Handle::weak_from_u128(i)`.
+    fn graph(
+        pass_through: &[bool],
+        transitions: &[(usize, usize, u32)],
+    ) -> GlyphAnimationGraphSource {
+        let mut graph = GlyphAnimationGraphSource {
+            state_names: HashMap::new(),
+            states: pass_through
+                .iter()
+                .map(|&pass_through| GlyphAnimationGraphState {
+                    animation: Handle::default(),
+                    pass_through,
+                })
+                .collect(),
+            transitions: vec![vec![]; pass_through.len()],
+        };
+        for (index, &(from, to, cost)) in transitions.iter().enumerate() {
+            graph.transitions[from].push(GlyphAnimationGraphTransition {
+                to,
+                cost,
+                animation: Some(Handle::weak_from_u128(index as u128)),
+            });
+        }
+        graph
+    }
+
+    fn animations(transitions: &[u128]) -> Option<VecDeque<Handle<GlyphAnimationSource>>> {
+        Some(
+            transitions
+                .iter()
+                .map(|&i| Handle::weak_from_u128(i))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn multi_hop_paths_play_in_order() {
+        let graph = graph(&[true; 4], &[(0, 1, 1), (1, 2, 1), (2, 3, 1)]);
+        assert_eq!(graph.traverse(0, 3).transitions, animations(&[0, 1, 2]));
+    }
+
+    #[test]
+    fn cheaper_longer_path_beats_costly_direct_one() {
+        let graph = graph(&[true; 3], &[(0, 2, 5), (0, 1, 1), (1, 2, 1)]);
+        assert_eq!(graph.traverse(0, 2).transitions, animations(&[1, 2]));
+    }
+
+    #[test]
+    fn equal_costs_prefer_fewer_hops() {
+        let graph = graph(&[true; 3], &[(0, 1, 1), (1, 2, 1), (0, 2, 2)]);
+        assert_eq!(graph.traverse(0, 2).transitions, animations(&[2]));
+    }
+
+    #[test]
+    fn states_without_pass_through_are_never_crossed() {
+        let graph = graph(&[true, false, true], &[(0, 1, 1), (1, 2, 1), (0, 2, 10)]);
+        assert_eq!(graph.traverse(0, 2).transitions, animations(&[2]));
+        assert_eq!(graph.traverse(0, 1).transitions, animations(&[0]));
+        assert_eq!(graph.traverse(1, 2).transitions, animations(&[1]));
+    }
+
+    #[test]
+    fn unreachable_destinations_have_no_transitions() {
+        let graph = graph(&[true; 3], &[(0, 1, 1), (1, 0, 1)]);
+        assert_eq!(graph.traverse(0, 2).transitions, None);
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use std::collections::VecDeque;
 
 use super::{GlyphAnimationGraph, GlyphAnimationGraphSource};
 use crate::glyph_animation::{GlyphAnimation, GlyphAnimationSource};
@@ -19,7 +20,7 @@ pub struct GlyphAnimationGraphTarget(pub Option<String>);
 
 #[derive(Debug, Component, Default, Clone)]
 pub struct GlyphAnimationGraphCurrent {
-    pub(crate) transitional_states: Vec<Handle<GlyphAnimationSource>>,
+    pub(crate) transitional_states: VecDeque<Handle<GlyphAnimationSource>>,
     pub(crate) current_state: usize,
     pub(crate) frame_timer: f32,
 }
@@ -38,42 +39,60 @@ pub fn animation_graph_player(
     glyph_animation_graphs: Res<Assets<GlyphAnimationGraphSource>>,
 ) {
     for (entity, graph, mut current, animation, settings) in q_players.iter_mut() {
-        if let Some(mut animation) = animation {
-            current.frame_timer += time.delta_secs() * settings.framerate;
-            let Some(animation_source) = glyph_animations.get(&animation.source) else {
-                continue;
-            };
-            if current.frame_timer > 1.0 {
-                animation.frame += current.frame_timer as u32;
-                current.frame_timer -= current.frame_timer.floor();
-            }
-            if animation.frame < animation_source.frames.len() as u32 {
-                continue;
-            }
+        if let Some(new_animation_component) = advance_graph_animation(
+            &graph,
+            &mut current,
+            animation.map(Mut::into_inner),
+            settings,
+            time.delta_secs(),
+            &glyph_animations,
+            &glyph_animation_graphs,
+        ) {
+            commands.entity(entity).insert(new_animation_component);
         }
+    }
+}
 
-        let Some(graph_source) = glyph_animation_graphs.get(&graph.source) else {
-            continue;
-        };
-        let new_animation_component =
-            if let Some(transition_animation) = current.transitional_states.pop() {
-                GlyphAnimation {
-                    frame: 0,
-                    source: transition_animation,
-                }
-            } else {
-                GlyphAnimation {
-                    frame: 0,
-                    source: graph_source
-                        .states
-                        .get(current.current_state)
-                        .unwrap()
-                        .animation
-                        .clone(),
-                }
-            };
-        commands.entity(entity).insert(new_animation_component);
+pub(crate) fn advance_graph_animation(
+    graph: &GlyphAnimationGraph,
+    current: &mut GlyphAnimationGraphCurrent,
+    animation: Option<&mut GlyphAnimation>,
+    settings: &GlyphAnimationGraphSettings,
+    delta_secs: f32,
+    glyph_animations: &Assets<GlyphAnimationSource>,
+    glyph_animation_graphs: &Assets<GlyphAnimationGraphSource>,
+) -> Option<GlyphAnimation> {
+    if let Some(animation) = animation {
+        current.frame_timer += delta_secs * settings.framerate;
+        let animation_source = glyph_animations.get(&animation.source)?;
+        if current.frame_timer > 1.0 {
+            animation.frame += current.frame_timer as u32;
+            current.frame_timer -= current.frame_timer.floor();
+        }
+        if animation.frame < animation_source.frames.len() as u32 {
+            return None;
+        }
     }
+
+    let graph_source = glyph_animation_graphs.get(&graph.source)?;
+    Some(
+        if let Some(transition_animation) = current.transitional_states.pop_front() {
+            GlyphAnimation {
+                frame: 0,
+                source: transition_animation,
+            }
+        } else {
+            GlyphAnimation {
+                frame: 0,
+                source: graph_source
+                    .states
+                    .get(current.current_state)
+                    .unwrap()
+                    .animation
+                    .clone(),
+            }
+        },
+    )
 }
 
 pub(crate) fn animation_graph_traverse(
@@ -85,20 +104,29 @@ pub(crate) fn animation_graph_traverse(
     glyph_animation_graphs: Res<Assets<GlyphAnimationGraphSource>>,
 ) {
     for (graph, mut current, target) in q_animation_graphs.iter_mut() {
-        let Some(target) = target.as_ref() else {
-            continue;
-        };
+        traverse_graph(&graph, &mut current, target, &glyph_animation_graphs);
+    }
+}
 
-        let Some(graph_source) = glyph_animation_graphs.get(&graph.source) else {
-            continue;
-        };
-        let target = *graph_source.state_names.get(target).unwrap();
+pub(crate) fn traverse_graph(
+    graph: &GlyphAnimationGraph,
+    current: &mut GlyphAnimationGraphCurrent,
+    target: &GlyphAnimationGraphTarget,
+    glyph_animation_graphs: &Assets<GlyphAnimationGraphSource>,
+) {
+    let Some(target) = target.as_ref() else {
+        return;
+    };
 
-        if current.current_state != target {
-            let transition = graph_source.traverse(current.current_state, target);
-            current.current_state = target;
+    let Some(graph_source) = glyph_animation_graphs.get(&graph.source) else {
+        return;
+    };
+    let target = *graph_source.state_names.get(target).unwrap();
 
-            current.transitional_states = transition.transitions.unwrap_or(vec![]);
-        }
+    if current.current_state != target {
+        let transition = graph_source.traverse(current.current_state, target);
+        current.current_state = target;
+
+        current.transitional_states = transition.transitions.unwrap_or_default();
     }
 }
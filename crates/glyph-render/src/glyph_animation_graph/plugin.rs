@@ -1,10 +1,15 @@
 use bevy::prelude::*;
 
 use super::{
+    layers::{animation_layers_player, composite_animation_layers, remove_animation_composites},
     player::{animation_graph_player, animation_graph_traverse},
     GlyphAnimationGraphAssetLoader, GlyphAnimationGraphSource,
 };
-use crate::glyph_animation::player::loop_animation_player;
+use crate::glyph_animation::{
+    anchors::{update_anchor_attachments, update_animation_anchors},
+    hitboxes::update_animation_hitboxes,
+    player::loop_animation_player,
+};
 
 pub struct GlyphAnimationGraphPlugin;
 
@@ -17,7 +22,36 @@ impl Plugin for GlyphAnimationGraphPlugin {
                 (
                     (animation_graph_traverse, animation_graph_player).chain(),
                     loop_animation_player,
+                    animation_layers_player,
+                    (remove_animation_composites, composite_animation_layers)
+                        .chain()
+                        .after(animation_graph_player)
+                        .after(loop_animation_player)
+                        .after(animation_layers_player),
+                    (update_animation_anchors, update_anchor_attachments)
+                        .chain()
+                        .after(animation_graph_player)
+                        .after(loop_animation_player)
+                        .before(TransformSystem::TransformPropagate),
+                    update_animation_hitboxes
+                        .after(animation_graph_player)
+                        .after(loop_animation_player),
                 ),
             );
+
+        #[cfg(feature = "hot_reload")]
+        app.add_systems(
+            PostUpdate,
+            (
+                super::reload::reload_animation_graphs
+                    .before(animation_graph_traverse)
+                    .before(animation_layers_player),
+                super::reload::reload_animation_composites
+                    .after(animation_graph_player)
+                    .after(loop_animation_player)
+                    .after(animation_layers_player)
+                    .before(remove_animation_composites),
+            ),
+        );
     }
 }
@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+
+use super::{
+    layers::{GlyphAnimationComposite, GlyphAnimationLayers},
+    player::GlyphAnimationGraphCurrent,
+    GlyphAnimationGraph, GlyphAnimationGraphSource,
+};
+use crate::glyph_animation::{GlyphAnimation, GlyphAnimationSource};
+
+// Restart graphs whose source changed, their state indices may no longer be valid.
+pub(crate) fn reload_animation_graphs(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<GlyphAnimationGraphSource>>,
+    mut q_graphs: Query<(
+        Entity,
+        &GlyphAnimationGraph,
+        &mut GlyphAnimationGraphCurrent,
+    )>,
+    mut q_layers: Query<&mut GlyphAnimationLayers>,
+) {
+    for event in events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+
+        for (entity, graph, mut current) in q_graphs.iter_mut() {
+            if graph.source.id() == *id {
+                *current = Default::default();
+                commands.entity(entity).remove::<GlyphAnimation>();
+            }
+        }
+        for mut layers in q_layers.iter_mut() {
+            for layer in layers.layers.iter_mut() {
+                if layer.graph.source.id() == *id {
+                    layer.current = Default::default();
+                    layer.animation = None;
+                }
+            }
+        }
+    }
+}
+
+// Composites are keyed on animation frames, so any modified animation invalidates them.
+pub(crate) fn reload_animation_composites(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<GlyphAnimationSource>>,
+    q_composites: Query<Entity, With<GlyphAnimationComposite>>,
+) {
+    if !events
+        .read()
+        .any(|event| matches!(event, AssetEvent::Modified { .. }))
+    {
+        return;
+    }
+
+    for entity in q_composites.iter() {
+        commands.entity(entity).remove::<GlyphAnimationComposite>();
+    }
+}
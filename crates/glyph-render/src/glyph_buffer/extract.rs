@@ -14,10 +14,11 @@ use crate::{
     atlas::FontAtlasCache,
     font::{CustomFont, CustomFontSource, FontSize},
     glyph_animation::{GlyphAnimation, GlyphAnimationSource},
+    glyph_animation_graph::layers::GlyphAnimationComposite,
     glyph_render_plugin::{
         ExtractedAtlas, SolidColor, GlyphSpriteMirrored, GlyphTexture, GlyphTextureSource,
     },
-    glyph_sprite::GlyphSprite,
+    glyph_sprite::{transform::GlyphSpriteTransformed, GlyphSprite},
     glyph_texture::{ExtractedGlyphTexture, ExtractedGlyphTextureCache},
 };
 use spatial_grid::{depth::Depth, global_position::GlobalPosition, grid::SpatialGrid};
@@ -43,7 +44,9 @@ pub fn extract_glyph_buffers(
             &GlobalPosition,
             Option<&GlyphSprite>,
             Option<&GlyphAnimation>,
+            Option<&GlyphAnimationComposite>,
             Option<&GlyphSpriteMirrored>,
+            Option<&GlyphSpriteTransformed>,
             Option<&SolidColor>,
             Option<&Depth>,
         )>,
@@ -78,20 +81,37 @@ pub fn extract_glyph_buffers(
         ));
 
         for entity in buffer.textures.iter() {
-            if let Ok((position, sprite, animation, mirrored, solid_color, depth)) =
-                q_textures.get(*entity)
+            if let Ok((
+                position,
+                sprite,
+                animation,
+                composite,
+                mirrored,
+                transformed,
+                solid_color,
+                depth,
+            )) = q_textures.get(*entity)
             {
                 if let Some(glyph_animation) = animation {
-                    let Some((data, offset)) = extract_animation_frame(
-                        &*glyph_animations,
-                        glyph_animation,
-                        mirrored.is_some(),
-                    ) else {
+                    let Some((data, offset)) = composite
+                        .map(|composite| (&composite.source, composite.offset))
+                        .or_else(|| {
+                            extract_animation_frame(
+                                &*glyph_animations,
+                                glyph_animation,
+                                mirrored.is_some(),
+                            )
+                        })
+                    else {
                         continue;
                     };
 
+                    let data = transformed
+                        .and_then(|transformed| transformed.get(data))
+                        .unwrap_or(data);
+
                     let extracted_glyph_texture = glyph_texture_cache.get_or_create(
-                        &data,
+                        data,
                         solid_color.map(|c| c.color).unwrap_or(Color::WHITE),
                         atlas,
                         font_source.as_ref(),
@@ -109,8 +129,12 @@ pub fn extract_glyph_buffers(
                         continue;
                     };
 
+                    let data = transformed
+                        .and_then(|transformed| transformed.get(&texture.source))
+                        .unwrap_or(&texture.source);
+
                     let extracted_glyph_texture = glyph_texture_cache.get_or_create(
-                        &texture.source,
+                        data,
                         solid_color.map(|c| c.color).unwrap_or(Color::WHITE),
                         atlas,
                         font_source.as_ref(),
@@ -134,14 +158,9 @@ fn extract_animation_frame<'a>(
     glyph_animation: &'a GlyphAnimation,
     mirrored: bool,
 ) -> Option<(&'a Arc<GlyphTextureSource>, IVec2)> {
-    let source = glyph_animations.get(&glyph_animation.source)?;
-    let (data, mirrored_data) = source.frames.get(glyph_animation.frame as usize)?;
+    let data = glyph_animations
+        .get(&glyph_animation.source)?
+        .frame(glyph_animation.frame, mirrored)?;
 
-    if mirrored {
-        let data = mirrored_data.as_ref().unwrap_or(data);
-
-        Some((&data.source, data.offset))
-    } else {
-        Some((&data.source, data.offset))
-    }
+    Some((&data.source, data.offset))
 }
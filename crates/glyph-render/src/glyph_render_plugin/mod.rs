@@ -14,6 +14,12 @@ use bytemuck::{cast_slice_mut, Pod, Zeroable};
 pub(crate) use node::GlyphGenerationNode;
 use spatial_grid::grid::SpatialGrid;
 use swash::FontRef;
+use text_util::{
+    text_flip::flip_lines,
+    text_markup::{parse_markup, MarkupStyle},
+    text_rotate::{rotate_lines, Rotation},
+    text_width::{cells_to_line, line_cells, lines_to_cells, WIDE_CHAR_PADDING},
+};
 
 use crate::{
     atlas::FontAtlasSource,
@@ -86,6 +92,8 @@ pub struct GlyphTextureSource {
     pub width: usize,
     pub height: usize,
     pub data: Box<[char]>,
+    /// Per cell colours, cells without one use the entity's `SolidColor`.
+    pub colors: Option<Box<[Option<Color>]>>,
 }
 
 impl GlyphTextureSource {
@@ -95,6 +103,7 @@ impl GlyphTextureSource {
             data,
             width,
             height,
+            colors: None,
         }
     }
     pub fn new_iter<I: IntoIterator<Item = char>>(width: usize, height: usize, iter: I) -> Self {
@@ -102,15 +111,94 @@ impl GlyphTextureSource {
             .into_iter()
             .take(width * height)
             .collect::<Box<[char]>>();
-        assert_eq!(data.len(), width * height);
-        Self {
-            data,
-            width,
-            height,
+        Self::new(width, height, data)
+    }
+    pub fn with_colors(mut self, colors: Box<[Option<Color>]>) -> Self {
+        assert_eq!(colors.len(), self.data.len());
+        self.colors = Some(colors);
+        self
+    }
+    /// Builds a texture from markup such as `[color=#ff6188]Hello[/]`, see `text_util::text_markup`.
+    pub fn from_markup(markup: &str, color: impl Fn(&MarkupStyle) -> Option<Color>) -> Self {
+        let mut rows: Vec<(Vec<char>, Vec<Option<Color>>)> = vec![Default::default()];
+        for span in parse_markup(markup) {
+            let span_color = color(&span.style);
+            for (index, line) in span.text.split('\n').enumerate() {
+                if index > 0 {
+                    rows.push(Default::default());
+                }
+                let (cells, colors) = rows.last_mut().unwrap();
+                let line_cells = line_cells(line);
+                colors.extend(std::iter::repeat_n(span_color, line_cells.len()));
+                cells.extend(line_cells);
+            }
+        }
+
+        let width = rows.iter().map(|(cells, _)| cells.len()).max().unwrap_or(0);
+        let height = rows.len();
+        let mut data = Vec::with_capacity(width * height);
+        let mut colors = Vec::with_capacity(width * height);
+        for (mut row_cells, mut row_colors) in rows {
+            row_cells.resize(width, ' ');
+            row_colors.resize(width, None);
+            data.extend(row_cells);
+            colors.extend(row_colors);
+        }
+
+        Self::new(width, height, data.into()).with_colors(colors.into())
+    }
+    pub fn lines(&self) -> Vec<String> {
+        self.data
+            .chunks(self.width.max(1))
+            .map(cells_to_line)
+            .collect()
+    }
+    pub fn flipped_y(&self) -> Self {
+        if self.data.is_empty() {
+            return self.clone();
+        }
+        let mut flipped: Self = (&flip_lines(&self.lines())).into();
+        flipped.colors = self
+            .colors
+            .as_ref()
+            .map(|colors| colors.chunks(self.width).rev().flatten().copied().collect());
+        flipped
+    }
+    // Returns `None` if any cell has no rotated counterpart.
+    pub fn rotated(&self, rotation: Rotation) -> Option<Self> {
+        if self.data.is_empty() {
+            return Some(self.clone());
         }
+        let mut rotated: Self = (&rotate_lines(&self.lines(), rotation)?).into();
+        rotated.colors = self.colors.as_ref().map(|colors| {
+            let (width, height) = (self.width, self.height);
+            let cell = |x: usize, y: usize| colors[x + y * width];
+            match rotation {
+                Rotation::Clockwise => (0..width)
+                    .flat_map(|x| (0..height).rev().map(move |y| (x, y)))
+                    .map(|(x, y)| cell(x, y))
+                    .collect(),
+                Rotation::Half => colors.iter().rev().copied().collect(),
+                Rotation::CounterClockwise => (0..width)
+                    .rev()
+                    .flat_map(|x| (0..height).map(move |y| (x, y)))
+                    .map(|(x, y)| cell(x, y))
+                    .collect(),
+            }
+        });
+        Some(rotated)
     }
 }
 
+/// Resolves `#rrggbb` markup colours.
+pub fn markup_color(style: &MarkupStyle) -> Option<Color> {
+    style
+        .color
+        .as_deref()
+        .and_then(|color| Srgba::hex(color).ok())
+        .map(Color::from)
+}
+
 #[derive(Asset, TypePath, Clone)]
 pub struct GlyphTexture {
     pub source: Arc<GlyphTextureSource>,
@@ -138,23 +226,8 @@ impl From<GlyphTextureSource> for GlyphTexture {
 
 impl From<&Vec<String>> for GlyphTextureSource {
     fn from(from: &Vec<String>) -> Self {
-        let height = from.len();
-        let width = from[0].chars().count();
-
-        let mut data: Box<[char]> = vec!['\0'; width * height].into_boxed_slice();
-        let mut index = 0;
-        for row in from.into_iter() {
-            for ch in row.chars() {
-                data[index] = ch;
-                index += 1;
-            }
-        }
-
-        Self {
-            width,
-            height,
-            data,
-        }
+        let (width, height, data) = lines_to_cells(from);
+        Self::new(width, height, data.into_boxed_slice())
     }
 }
 
@@ -191,7 +264,9 @@ impl ExtractedGlyphTextureSource {
         let mut data: Box<[u8]> = vec![0; 4 * 4 * texture.width * texture.height].into();
         let charmap = font.charmap();
 
+        // Wide glyphs extend over their padding cell, which is kept blank like a space.
         for (source_index, c) in texture.data.iter().copied().enumerate() {
+            let c = if c == WIDE_CHAR_PADDING { ' ' } else { c };
             let x = source_index % texture.width;
             let y = texture.height - source_index / texture.width - 1;
             let position = x + texture.width * y;
@@ -203,6 +278,11 @@ impl ExtractedGlyphTextureSource {
                 .unwrap_or(&if c == '·' { u16::MAX - 1 } else { u16::MAX })
                 .to_le_bytes();
 
+            let color = texture
+                .colors
+                .as_ref()
+                .and_then(|colors| colors[source_index])
+                .unwrap_or(color);
             data[index + 4..index + 16].copy_from_slice(cast_slice_mut(
                 &mut color.to_srgba().to_f32_array_no_alpha(),
             ));
@@ -261,6 +341,12 @@ pub(crate) struct GlyphModelUniformBuffer(pub(crate) UniformBuffer<GlyphModelUni
 #[derive(Debug, Component, Clone)]
 pub struct GlyphSpriteMirrored;
 
+#[derive(Debug, Component, Clone)]
+pub struct GlyphSpriteFlippedY;
+
+#[derive(Debug, Component, Clone, Copy, Deref)]
+pub struct GlyphSpriteRotation(pub Rotation);
+
 fn prepare_atlas_buffers(
     mut commands: Commands,
     render_device: Res<RenderDevice>,
@@ -2,6 +2,7 @@ use anyhow::Ok;
 use bevy::asset::AssetLoader;
 
 use crate::glyph_render_plugin::GlyphTexture;
+use text_util::{compression::Compression, text_art::parse_art_lines};
 
 pub(super) struct GlyphTextureLoader;
 
@@ -11,24 +12,20 @@ impl AssetLoader for GlyphTextureLoader {
     type Error = anyhow::Error;
 
     fn extensions(&self) -> &[&str] {
-        &["art"]
+        &["art", "art.gz", "art.zst"]
     }
     fn load(
         &self,
         reader: &mut dyn bevy::asset::io::Reader,
         _settings: &Self::Settings,
-        _load_context: &mut bevy::asset::LoadContext,
+        load_context: &mut bevy::asset::LoadContext,
     ) -> impl bevy::utils::ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
         Box::pin(async {
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
+            let bytes = Compression::from_path(load_context.path()).decompress(bytes)?;
 
-            let data = bytes
-                .split(|&b| b == b'\n')
-                .map(|line| {
-                    String::from_utf8(line.strip_suffix(b"\r").unwrap_or(line).to_vec()).unwrap()
-                })
-                .collect::<Vec<_>>();
+            let data = parse_art_lines(&bytes)?;
 
             Ok(data.into())
         })
@@ -1,9 +1,15 @@
 use bevy::prelude::*;
 
-use self::loader::GlyphTextureLoader;
-use crate::glyph_render_plugin::GlyphTexture;
+use self::{
+    loader::GlyphTextureLoader,
+    transform::{remove_sprite_transforms, update_sprite_transforms},
+};
+use crate::{
+    glyph_animation_graph::layers::composite_animation_layers, glyph_render_plugin::GlyphTexture,
+};
 
 pub(crate) mod loader;
+pub mod transform;
 
 #[derive(Component, Clone)]
 pub struct GlyphSprite {
@@ -15,6 +21,11 @@ pub struct GlyphTexturePlugin;
 
 impl Plugin for GlyphTexturePlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
-        app.register_asset_loader(GlyphTextureLoader);
+        app.register_asset_loader(GlyphTextureLoader).add_systems(
+            PostUpdate,
+            (remove_sprite_transforms, update_sprite_transforms)
+                .chain()
+                .after(composite_animation_layers),
+        );
     }
 }
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use std::sync::{Arc, Weak};
+use text_util::text_rotate::Rotation;
+
+use super::GlyphSprite;
+use crate::{
+    glyph_animation::{GlyphAnimation, GlyphAnimationSource},
+    glyph_animation_graph::layers::GlyphAnimationComposite,
+    glyph_render_plugin::{
+        GlyphSpriteFlippedY, GlyphSpriteMirrored, GlyphSpriteRotation, GlyphTexture,
+        GlyphTextureSource,
+    },
+};
+
+/// Flipped and rotated copy of the texture an entity currently draws.
+///
+/// Only used during extraction while `input` still matches the drawn texture.
+#[derive(Debug, Component, Clone)]
+pub struct GlyphSpriteTransformed {
+    pub(crate) source: Arc<GlyphTextureSource>,
+    input: Weak<GlyphTextureSource>,
+    flipped: bool,
+    rotation: Option<Rotation>,
+}
+
+impl GlyphSpriteTransformed {
+    pub(crate) fn get(&self, input: &Arc<GlyphTextureSource>) -> Option<&Arc<GlyphTextureSource>> {
+        Weak::ptr_eq(&self.input, &Arc::downgrade(input)).then_some(&self.source)
+    }
+}
+
+type TransformSourceQueryData<'a> = (
+    Entity,
+    Option<&'a GlyphSprite>,
+    Option<&'a GlyphAnimation>,
+    Option<&'a GlyphAnimationComposite>,
+    Has<GlyphSpriteMirrored>,
+    Has<GlyphSpriteFlippedY>,
+    Option<&'a GlyphSpriteRotation>,
+    Option<&'a GlyphSpriteTransformed>,
+);
+
+type TransformedFilter = Or<(With<GlyphSpriteFlippedY>, With<GlyphSpriteRotation>)>;
+
+type StaleTransformFilter = (
+    With<GlyphSpriteTransformed>,
+    Without<GlyphSpriteFlippedY>,
+    Without<GlyphSpriteRotation>,
+);
+
+pub(crate) fn update_sprite_transforms(
+    mut commands: Commands,
+    q_sprites: Query<TransformSourceQueryData, TransformedFilter>,
+    glyph_textures: Res<Assets<GlyphTexture>>,
+    glyph_animations: Res<Assets<GlyphAnimationSource>>,
+) {
+    for (entity, sprite, animation, composite, mirrored, flipped, rotation, transformed) in
+        q_sprites.iter()
+    {
+        let input = if let Some(animation) = animation {
+            composite.map(|composite| &composite.source).or_else(|| {
+                glyph_animations
+                    .get(&animation.source)
+                    .and_then(|source| source.frame(animation.frame, mirrored))
+                    .map(|frame| &frame.source)
+            })
+        } else {
+            sprite
+                .and_then(|sprite| glyph_textures.get(&sprite.texture))
+                .map(|texture| &texture.source)
+        };
+        let Some(input) = input else {
+            continue;
+        };
+
+        let rotation = rotation.map(|rotation| **rotation);
+        if transformed.is_some_and(|transformed| {
+            transformed.get(input).is_some()
+                && transformed.flipped == flipped
+                && transformed.rotation == rotation
+        }) {
+            continue;
+        }
+
+        let mut source = if flipped {
+            input.flipped_y()
+        } else {
+            (**input).clone()
+        };
+        if let Some(rotation) = rotation {
+            source = source.rotated(rotation).unwrap_or_else(|| {
+                warn!("Glyph texture of {entity} contains cells that can't be rotated");
+                source
+            });
+        }
+
+        commands.entity(entity).insert(GlyphSpriteTransformed {
+            source: Arc::new(source),
+            input: Arc::downgrade(input),
+            flipped,
+            rotation,
+        });
+    }
+}
+
+pub(crate) fn remove_sprite_transforms(
+    mut commands: Commands,
+    q_transformed: Query<Entity, StaleTransformFilter>,
+) {
+    for entity in q_transformed.iter() {
+        commands.entity(entity).remove::<GlyphSpriteTransformed>();
+    }
+}
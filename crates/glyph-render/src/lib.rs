@@ -7,3 +7,4 @@ pub mod glyph_buffer;
 pub mod glyph_render_plugin;
 pub mod glyph_sprite;
 pub mod glyph_texture;
+pub mod mirror_map;
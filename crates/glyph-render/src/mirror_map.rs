@@ -0,0 +1,89 @@
+use bevy::asset::LoadContext;
+use serde::Deserialize;
+use std::path::Path;
+use text_util::{
+    compression::Compression,
+    text_mirror::{mirror_lines_with, mirror_map, mirror_map_from, MirrorChars},
+};
+
+/// Character pairs swapped when mirroring art, read from a `.mirror.ron` file:
+///
+/// `(pairs: [('⌐', '¬')])`
+///
+/// Pairs extend the default map unless `extend_default` is `false`.
+#[derive(Deserialize)]
+struct MirrorMapMeta {
+    #[serde(default = "default_extend")]
+    extend_default: bool,
+    pairs: Vec<(char, char)>,
+}
+
+fn default_extend() -> bool {
+    true
+}
+
+#[derive(Debug, Clone)]
+pub struct MirrorMap(MirrorChars);
+
+impl Default for MirrorMap {
+    fn default() -> Self {
+        Self(mirror_map())
+    }
+}
+
+impl MirrorMap {
+    pub fn mirror_lines(&self, lines: &[String]) -> Vec<String> {
+        mirror_lines_with(lines, &self.0)
+    }
+
+    pub async fn load(
+        load_context: &mut LoadContext<'_>,
+        path: Option<&String>,
+    ) -> anyhow::Result<Self> {
+        let Some(path) = path else {
+            return Ok(Self::default());
+        };
+
+        let bytes = Compression::from_path(Path::new(path))
+            .decompress(load_context.read_asset_bytes(path).await?)?;
+        Ok(ron::de::from_bytes::<MirrorMapMeta>(&bytes)?.into())
+    }
+}
+
+impl From<MirrorMapMeta> for MirrorMap {
+    fn from(meta: MirrorMapMeta) -> Self {
+        let mut map = if meta.extend_default {
+            mirror_map()
+        } else {
+            MirrorChars::default()
+        };
+        map.extend(mirror_map_from(meta.pairs));
+        Self(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mirror_map(meta: &str) -> MirrorMap {
+        ron::de::from_str::<MirrorMapMeta>(meta).unwrap().into()
+    }
+
+    fn mirror(map: &MirrorMap, line: &str) -> String {
+        map.mirror_lines(&[line.to_string()]).remove(0)
+    }
+
+    #[test]
+    fn custom_pairs_extend_the_defaults() {
+        let map = mirror_map("(pairs: [('a', 'e'), ('<', '|')])");
+        assert_eq!(mirror(&map, "a(e"), "a)e");
+        assert_eq!(mirror(&map, "<|>"), "<<|");
+    }
+
+    #[test]
+    fn custom_pairs_can_replace_the_defaults() {
+        let map = mirror_map("(extend_default: false, pairs: [('a', 'e')])");
+        assert_eq!(mirror(&map, "a(/"), "/(e");
+    }
+}
@@ -1,17 +1,21 @@
 use super::{
-    collision::Collider,
+    collision::{Collider, CollisionFilter},
+    contact::ActorContactCache,
+    grid::{grid_enabled, grid_of, DisabledGrids},
     movement::{Movement, MovementObstructed},
-    solid::{Solid, SolidCollisionCache},
+    one_way::DropThrough,
+    solid::{Solid, SolidCollisionCache, SolidCollisionCaches},
 };
 use bevy_ecs::{
     bundle::Bundle,
     component::Component,
     entity::Entity,
-    query::{With, Without},
-    system::{Commands, Query, Res},
+    query::{Has, With, Without},
+    system::{Commands, Query, Res, ResMut},
 };
 use bevy_math::{IVec2, Vec2};
 use spatial_grid::{
+    grid::PhysicsGridMember,
     position::{Position, SpatialBundle},
     remainder::Remainder,
 };
@@ -21,6 +25,16 @@ pub type FilterActors = (With<Actor>, Without<Solid>);
 #[derive(Component, Default, Clone)]
 pub struct Actor;
 
+/// Ledges an actor climbs or steps down without jumping while it walks on something.
+///
+/// Slopes drawn with `/` and `\` have no special handling, they're only walkable
+/// as one cell ledges, so their art has to be stair-stepped.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct StepHeight {
+    pub up: u32,
+    pub down: u32,
+}
+
 impl Actor {
     pub(crate) fn move_x(
         amount: f32,
@@ -28,6 +42,8 @@ impl Actor {
         actor_remainder: &mut Remainder,
         actor_collider: &Collider,
         collision_cache: &SolidCollisionCache,
+        filter: CollisionFilter,
+        step_height: StepHeight,
     ) -> Option<Entity> {
         actor_remainder.x += amount;
         let mut movement: i32 = actor_remainder.x.round() as i32;
@@ -35,14 +51,37 @@ impl Actor {
             actor_remainder.x -= movement as f32;
             let step = movement.signum();
             while movement != 0 {
-                if let Some(solid) =
-                    actor_collider.overlaps(**actor_position + IVec2::X * step, collision_cache)
-                {
-                    return Some(solid);
+                let blocked = |position: IVec2, step: IVec2| {
+                    actor_collider.blocked(position, step, collision_cache, filter)
+                };
+                let grounded = (step_height.up > 0 || step_height.down > 0)
+                    && blocked(**actor_position, IVec2::NEG_Y).is_some();
+
+                if let Some(solid) = blocked(**actor_position, IVec2::X * step) {
+                    // Climb the lowest ledge that fits under the ceiling, in the air walls block.
+                    let max_rise = if grounded { step_height.up as i32 } else { 0 };
+                    let rise = (1..=max_rise)
+                        .take_while(|rise| {
+                            blocked(**actor_position + IVec2::Y * (rise - 1), IVec2::Y).is_none()
+                        })
+                        .find(|rise| {
+                            blocked(**actor_position + IVec2::Y * rise, IVec2::X * step).is_none()
+                        });
+                    let Some(rise) = rise else {
+                        return Some(solid);
+                    };
+                    **actor_position += IVec2::new(step, rise);
                 } else {
                     actor_position.x += step;
-                    movement -= step;
+                    if grounded {
+                        // Stay on the ground walking down ledges, walking off higher ones falls.
+                        let drop = (0..=step_height.down as i32).find(|drop| {
+                            blocked(**actor_position - IVec2::Y * drop, IVec2::NEG_Y).is_some()
+                        });
+                        actor_position.y -= drop.unwrap_or(0);
+                    }
                 }
+                movement -= step;
             }
         }
         None
@@ -53,6 +92,7 @@ impl Actor {
         actor_remainder: &mut Remainder,
         actor_collider: &Collider,
         collision_cache: &SolidCollisionCache,
+        filter: CollisionFilter,
     ) -> Option<Entity> {
         actor_remainder.y += amount;
         let mut movement: i32 = actor_remainder.y.round() as i32;
@@ -60,9 +100,12 @@ impl Actor {
             actor_remainder.y -= movement as f32;
             let step = movement.signum();
             while movement != 0 {
-                if let Some(solid) =
-                    actor_collider.overlaps(**actor_position + IVec2::Y * step, collision_cache)
-                {
+                if let Some(solid) = actor_collider.blocked(
+                    **actor_position,
+                    IVec2::Y * step,
+                    collision_cache,
+                    filter,
+                ) {
                     return Some(solid);
                 } else {
                     actor_position.y += step;
@@ -79,6 +122,8 @@ impl Actor {
         actor_remainder: &Remainder,
         actor_collider: &Collider,
         collision_cache: &SolidCollisionCache,
+        filter: CollisionFilter,
+        step_height: StepHeight,
     ) -> Option<Entity> {
         Self::move_x(
             amount,
@@ -86,6 +131,8 @@ impl Actor {
             &mut actor_remainder.clone(),
             actor_collider,
             collision_cache,
+            filter,
+            step_height,
         )
     }
     pub fn test_move_y(
@@ -94,6 +141,7 @@ impl Actor {
         actor_remainder: &Remainder,
         actor_collider: &Collider,
         collision_cache: &SolidCollisionCache,
+        filter: CollisionFilter,
     ) -> Option<Entity> {
         Self::move_y(
             amount,
@@ -101,6 +149,7 @@ impl Actor {
             &mut actor_remainder.clone(),
             actor_collider,
             collision_cache,
+            filter,
         )
     }
 }
@@ -113,23 +162,42 @@ pub struct ActorPhysicsBundle {
     pub movement: Movement,
 }
 
+type MovingActorQueryData<'a> = (
+    Entity,
+    &'a mut Position,
+    &'a mut Remainder,
+    &'a mut Movement,
+    &'a Collider,
+    Option<&'a PhysicsGridMember>,
+    Has<DropThrough>,
+    Option<&'a StepHeight>,
+);
+
 pub(super) fn actor_move_system(
-    mut q_actors: Query<
-        (
-            Entity,
-            &mut Position,
-            &mut Remainder,
-            &mut Movement,
-            &Collider,
-        ),
-        FilterActors,
-    >,
+    mut q_actors: Query<MovingActorQueryData, FilterActors>,
     mut commands: Commands,
-    solid_collision_cache: Res<SolidCollisionCache>,
+    q_disabled: DisabledGrids,
+    solid_collision_caches: Res<SolidCollisionCaches>,
+    mut contact_cache: ResMut<ActorContactCache>,
 ) {
-    for (entity, mut actor_position, mut actor_remainder, mut actor_movement, actor_collider) in
-        q_actors.iter_mut()
+    for (
+        entity,
+        mut actor_position,
+        mut actor_remainder,
+        mut actor_movement,
+        actor_collider,
+        member,
+        drop_through,
+        step_height,
+    ) in q_actors.iter_mut()
     {
+        let grid = grid_of(member);
+        if !grid_enabled(&q_disabled, grid) {
+            continue;
+        }
+        let solid_collision_cache = solid_collision_caches.get(grid);
+        let filter = CollisionFilter::new(drop_through)
+            .with_actors(contact_cache.blocking_query(entity, member));
         let mut obstructed = MovementObstructed::default();
 
         if let Some(solid) = Actor::move_x(
@@ -137,7 +205,9 @@ pub(super) fn actor_move_system(
             &mut actor_position,
             &mut actor_remainder,
             actor_collider,
-            &solid_collision_cache,
+            solid_collision_cache,
+            filter,
+            step_height.copied().unwrap_or_default(),
         ) {
             if actor_movement.delta.x > 0.0 {
                 obstructed.x = Some(solid);
@@ -150,7 +220,8 @@ pub(super) fn actor_move_system(
             &mut actor_position,
             &mut actor_remainder,
             actor_collider,
-            &solid_collision_cache,
+            solid_collision_cache,
+            filter,
         ) {
             if actor_movement.delta.y > 0.0 {
                 obstructed.y = Some(solid);
@@ -158,7 +229,62 @@ pub(super) fn actor_move_system(
                 obstructed.neg_y = Some(solid);
             }
         }
+        contact_cache.update(entity, member, &actor_position, actor_collider);
         commands.entity(entity).insert(obstructed);
         actor_movement.delta = Vec2::ZERO;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::UVec2;
+
+    use super::*;
+    use crate::{
+        collision::{Aabb, CollisionLayers},
+        test_util::collider,
+    };
+
+    fn walk_right(position: IVec2, solids: &[(IVec2, UVec2)]) -> (IVec2, Option<Entity>) {
+        let mut cache = SolidCollisionCache::default();
+        for (index, &(start, size)) in solids.iter().enumerate() {
+            cache.insert(
+                Entity::from_raw(index as u32),
+                vec![Aabb { start, size }],
+                CollisionLayers::default(),
+                None,
+            );
+        }
+
+        let mut position = Position(position);
+        let blocked = Actor::move_x(
+            1.0,
+            &mut position,
+            &mut Remainder::default(),
+            &collider(UVec2::ONE),
+            &cache,
+            CollisionFilter::default(),
+            StepHeight { up: 1, down: 1 },
+        );
+        (*position, blocked)
+    }
+
+    #[test]
+    fn grounded_actors_step_up_ledges() {
+        let floor = (IVec2::new(-4, -1), UVec2::new(8, 1));
+        let ledge = (IVec2::X, UVec2::ONE);
+        assert_eq!(
+            walk_right(IVec2::ZERO, &[floor, ledge]),
+            (IVec2::new(1, 1), None)
+        );
+    }
+
+    #[test]
+    fn airborne_actors_are_blocked_by_ledges() {
+        let ledge = (IVec2::X, UVec2::ONE);
+        assert_eq!(
+            walk_right(IVec2::ZERO, &[ledge]),
+            (IVec2::ZERO, Some(Entity::from_raw(0)))
+        );
+    }
+}
@@ -1,14 +1,49 @@
-use core::f32;
+use std::collections::HashMap;
 
-use bevy_ecs::{component::Component, entity::Entity};
-use bevy_math::{IVec2, UVec2, Vec2};
-use spatial_grid::{direction::Direction, position::Position};
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityHashMap},
+};
+use bevy_math::{IVec2, UVec2};
+use spatial_grid::direction::Direction;
 
-use super::solid::SolidCollisionCache;
+use super::{contact::ContactQuery, solid::SolidCollisionCache};
 
 #[derive(Component, Default, Clone)]
 pub struct Collider {
     pub shape: CompositeCollisionShape,
+    pub layers: CollisionLayers,
+}
+
+/// Two colliders only collide if each one's `member` shares a bit with the other's `filter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionLayers {
+    pub member: u32,
+    pub filter: u32,
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self {
+            member: 1,
+            filter: u32::MAX,
+        }
+    }
+}
+
+impl CollisionLayers {
+    pub const NONE: Self = Self {
+        member: 0,
+        filter: 0,
+    };
+
+    pub fn new(member: u32, filter: u32) -> Self {
+        Self { member, filter }
+    }
+
+    pub fn interacts(&self, other: &Self) -> bool {
+        self.member & other.filter != 0 && other.member & self.filter != 0
+    }
 }
 
 impl Collider {
@@ -30,70 +65,6 @@ impl Collider {
     }
 }
 
-impl RayTest for (&Position, &Collider) {
-    fn test_ray(&self, origin: IVec2, direction_inv: Vec2) -> Option<(f32, f32)> {
-        let mut min: Option<f32> = None;
-        let mut max: Option<f32> = None;
-
-        for (ray_min, ray_max) in self
-            .1
-            .shape
-            .iter_at(self.0 .0)
-            .filter_map(|aabb| RayTest::test_ray(&aabb, origin, direction_inv))
-        {
-            if ray_min.is_finite() {
-                if let Some(t_min) = min {
-                    min = Some(t_min.min(ray_min));
-                } else {
-                    min = Some(ray_min)
-                };
-            }
-
-            if ray_max.is_finite() {
-                if let Some(t_max) = max {
-                    max = Some(t_max.max(ray_max));
-                } else {
-                    max = Some(ray_max)
-                };
-            }
-        }
-
-        if let (Some(min), Some(max)) = (min, max) {
-            Some((min, max))
-        } else {
-            None
-        }
-    }
-}
-
-pub trait RayTest {
-    fn test_ray(&self, origin: IVec2, direction_inv: Vec2) -> Option<(f32, f32)>;
-}
-
-impl RayTest for Aabb {
-    fn test_ray(&self, origin: IVec2, direction_inv: Vec2) -> Option<(f32, f32)> {
-        let tmin = 0f32;
-        let tmax = f32::INFINITY;
-
-        let tx1 = (self.start.x - origin.x) as f32 * direction_inv.x;
-        let tx2 = (self.start.x + self.size.x as i32 - origin.x) as f32 * direction_inv.x;
-        let ty1 = (self.start.y - origin.y) as f32 * direction_inv.y;
-        let ty2 = (self.start.y + self.size.y as i32 - origin.y) as f32 * direction_inv.y;
-
-        let tmin = f32::min(tx1.max(tmin), tx2.max(tmin));
-        let tmax = f32::max(tx1.min(tmax), tx2.min(tmax));
-
-        let tmin = f32::min(ty1.max(tmin), ty2.max(tmin));
-        let tmax = f32::max(ty1.min(tmax), ty2.min(tmax));
-
-        if tmax >= tmin {
-            Some((tmin, tmax))
-        } else {
-            None
-        }
-    }
-}
-
 pub(crate) trait Overlaps<T> {
     fn overlaps(&self, other: T) -> bool;
     fn overlap_distance(&self, other: T, direction: Direction) -> Option<i32>;
@@ -111,9 +82,9 @@ impl Overlaps<&Aabb> for Aabb {
         if self.overlaps(other) {
             Some(match direction {
                 Direction::PosX => other.start.x + other.size.x as i32 - self.start.x,
-                Direction::PosY => self.start.y + self.size.y as i32 - other.start.y,
+                Direction::PosY => other.start.y + other.size.y as i32 - self.start.y,
                 Direction::NegX => self.start.x + self.size.x as i32 - other.start.x,
-                Direction::NegY => self.start.y - other.start.y - other.size.y as i32,
+                Direction::NegY => self.start.y + self.size.y as i32 - other.start.y,
             })
         } else {
             None
@@ -127,11 +98,46 @@ pub struct CompositeCollisionShape {
 }
 
 impl CompositeCollisionShape {
-    pub fn iter_at<'a>(&'a self, offset: IVec2) -> impl Iterator<Item = Aabb> + '_ {
+    pub fn iter_at(&self, offset: IVec2) -> impl Iterator<Item = Aabb> + '_ {
         self.shapes.iter().map(move |shape| shape.translate(offset))
     }
 }
 
+impl CompositeCollisionShape {
+    /// Merges the solid cells of a `size` grid into rectangles, sweeping rows from the bottom.
+    pub fn from_cells(size: UVec2, solid: impl Fn(UVec2) -> bool) -> Self {
+        let (width, height) = (size.x as usize, size.y as usize);
+        let mut covered = vec![false; width * height];
+        let free = |covered: &[bool], x: usize, y: usize| {
+            !covered[x + y * width] && solid(UVec2::new(x as u32, y as u32))
+        };
+
+        let mut shapes = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                if !free(&covered, x, y) {
+                    continue;
+                }
+                let run = (x..width).take_while(|&x| free(&covered, x, y)).count();
+                let rows = (y..height)
+                    .take_while(|&y| (x..x + run).all(|x| free(&covered, x, y)))
+                    .count();
+                for dy in y..y + rows {
+                    covered[x + dy * width..x + run + dy * width].fill(true);
+                }
+                shapes.push(Aabb {
+                    start: IVec2::new(x as i32, y as i32),
+                    size: UVec2::new(run as u32, rows as u32),
+                });
+            }
+        }
+
+        Self {
+            shapes: shapes.into_boxed_slice(),
+        }
+    }
+}
+
 impl Default for CompositeCollisionShape {
     fn default() -> Self {
         Self {
@@ -159,34 +165,167 @@ impl Aabb {
     }
 }
 
-impl Into<Collider> for Aabb {
-    fn into(self) -> Collider {
+impl From<Aabb> for Collider {
+    fn from(aabb: Aabb) -> Self {
         Collider {
             shape: CompositeCollisionShape {
-                shapes: Box::new([self]),
+                shapes: Box::new([aabb]),
             },
+            layers: CollisionLayers::default(),
         }
     }
 }
 
+/// What blocks an actor besides solids.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CollisionFilter<'a> {
+    /// Pass through one-way solids in any direction, see `DropThrough`.
+    pub drop_through: bool,
+    /// Blocking actors, see `ActorContact::blocking`.
+    actors: Option<ContactQuery<'a>>,
+}
+
+impl<'a> CollisionFilter<'a> {
+    pub fn new(drop_through: bool) -> Self {
+        Self {
+            drop_through,
+            actors: None,
+        }
+    }
+
+    pub(crate) fn with_actors(mut self, actors: Option<ContactQuery<'a>>) -> Self {
+        self.actors = actors;
+        self
+    }
+}
+
+/// Collider AABBs bucketed on a uniform grid so overlap tests only visit nearby colliders.
+#[derive(Debug, Default)]
+pub struct ColliderCache {
+    collisions: EntityHashMap<Vec<Aabb>>,
+    buckets: HashMap<IVec2, Vec<(Entity, usize)>>,
+    layers: EntityHashMap<CollisionLayers>,
+}
+
+const BUCKET_SIZE: i32 = 16;
+
+impl ColliderCache {
+    pub(crate) fn insert(&mut self, entity: Entity, aabbs: Vec<Aabb>, layers: CollisionLayers) {
+        self.remove(entity);
+        self.layers.insert(entity, layers);
+        for (index, aabb) in aabbs.iter().enumerate() {
+            for bucket in Self::buckets_of(aabb) {
+                self.buckets
+                    .entry(bucket)
+                    .or_default()
+                    .push((entity, index));
+            }
+        }
+        self.collisions.insert(entity, aabbs);
+    }
+
+    pub(crate) fn remove(&mut self, entity: Entity) {
+        self.layers.remove(&entity);
+        let Some(aabbs) = self.collisions.remove(&entity) else {
+            return;
+        };
+        for bucket in aabbs.iter().flat_map(Self::buckets_of) {
+            if let Some(entries) = self.buckets.get_mut(&bucket) {
+                entries.retain(|(other, _)| *other != entity);
+                if entries.is_empty() {
+                    self.buckets.remove(&bucket);
+                }
+            }
+        }
+    }
+
+    /// AABBs sharing a bucket with `aabb`, the same AABB may be returned more than once.
+    pub(crate) fn nearby<'a>(&'a self, aabb: &Aabb) -> impl Iterator<Item = (Entity, &'a Aabb)> {
+        Self::buckets_of(aabb)
+            .filter_map(|bucket| self.buckets.get(&bucket))
+            .flatten()
+            .map(|&(entity, index)| (entity, &self.collisions[&entity][index]))
+    }
+
+    pub(crate) fn layers(&self, entity: Entity) -> CollisionLayers {
+        self.layers.get(&entity).copied().unwrap_or_default()
+    }
+
+    fn buckets_of(aabb: &Aabb) -> impl Iterator<Item = IVec2> {
+        let min = aabb.start.div_euclid(IVec2::splat(BUCKET_SIZE));
+        let max = (aabb.start + aabb.size.as_ivec2().max(IVec2::ONE) - IVec2::ONE)
+            .div_euclid(IVec2::splat(BUCKET_SIZE));
+        (min.x..=max.x).flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+    }
+}
+
 impl Collider {
+    /// The first solid overlapping the collider at `self_pos`, one-way solids are ignored.
     pub(crate) fn overlaps(&self, self_pos: IVec2, other: &SolidCollisionCache) -> Option<Entity> {
         let self_colliders = self.shape.iter_at(self_pos);
 
         for actor_aabb in self_colliders {
-            for (solid, solid_aabb) in other
-                .collisions
-                .iter()
-                .flat_map(|(solid, collider)| collider.iter().map(|aabb| (*solid, aabb)))
-            {
-                if actor_aabb.overlaps(solid_aabb) {
+            for (solid, solid_aabb) in other.nearby(&actor_aabb) {
+                if other.one_way(solid).is_none()
+                    && self.layers.interacts(&other.layers(solid))
+                    && actor_aabb.overlaps(solid_aabb)
+                {
+                    return Some(solid);
+                }
+            }
+        }
+        None
+    }
+
+    /// The first solid blocking a single cell `step` from `self_pos`.
+    pub(crate) fn blocked(
+        &self,
+        self_pos: IVec2,
+        step: IVec2,
+        other: &SolidCollisionCache,
+        filter: CollisionFilter,
+    ) -> Option<Entity> {
+        for actor_aabb in self.shape.iter_at(self_pos + step) {
+            for (solid, solid_aabb) in other.nearby(&actor_aabb) {
+                if !self.layers.interacts(&other.layers(solid)) || !actor_aabb.overlaps(solid_aabb)
+                {
+                    continue;
+                }
+                let Some(direction) = other.one_way(solid) else {
+                    return Some(solid);
+                };
+                if filter.drop_through || step.dot(direction.as_ivec2()) >= 0 {
+                    continue;
+                }
+                // Actors already inside a one-way solid may leave it in any direction.
+                if !self.overlaps_aabb(self_pos, solid_aabb) {
                     return Some(solid);
                 }
             }
+
+            let Some(actors) = filter.actors else {
+                continue;
+            };
+            for (actor, actor_aabb_other) in actors.cache.nearby(&actor_aabb) {
+                // Overlapping actors may move apart.
+                if actors.interacts(actor)
+                    && self.layers.interacts(&actors.cache.layers(actor))
+                    && actor_aabb.overlaps(actor_aabb_other)
+                    && !self.overlaps_aabb(self_pos, actor_aabb_other)
+                {
+                    return Some(actor);
+                }
+            }
         }
         None
     }
 
+    fn overlaps_aabb(&self, self_pos: IVec2, other: &Aabb) -> bool {
+        self.shape
+            .iter_at(self_pos)
+            .any(|aabb| aabb.overlaps(other))
+    }
+
     pub(crate) fn overlap_distance(
         &self,
         self_pos: IVec2,
@@ -220,3 +359,69 @@ impl From<Aabb> for CompositeCollisionShape {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aabb(x: i32, y: i32, width: u32, height: u32) -> Aabb {
+        Aabb {
+            start: IVec2::new(x, y),
+            size: UVec2::new(width, height),
+        }
+    }
+
+    fn nearby(cache: &ColliderCache, aabb: &Aabb) -> Vec<Entity> {
+        let mut entities = cache
+            .nearby(aabb)
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>();
+        entities.sort();
+        entities.dedup();
+        entities
+    }
+
+    #[test]
+    fn cache_buckets_negative_coordinates() {
+        let mut cache = ColliderCache::default();
+        let entity = Entity::from_raw(0);
+        cache.insert(entity, vec![aabb(-17, -1, 1, 1)], Default::default());
+
+        assert_eq!(nearby(&cache, &aabb(-32, -16, 1, 1)), [entity]);
+        assert_eq!(nearby(&cache, &aabb(-16, -1, 1, 1)), []);
+        assert_eq!(nearby(&cache, &aabb(-17, 0, 1, 1)), []);
+    }
+
+    #[test]
+    fn cache_finds_aabbs_spanning_buckets() {
+        let mut cache = ColliderCache::default();
+        let entity = Entity::from_raw(0);
+        cache.insert(entity, vec![aabb(10, 10, 30, 10)], Default::default());
+
+        for query in [aabb(0, 0, 1, 1), aabb(47, 31, 1, 1), aabb(-5, 15, 10, 10)] {
+            assert_eq!(nearby(&cache, &query), [entity]);
+        }
+        assert_eq!(nearby(&cache, &aabb(48, 10, 1, 1)), []);
+        assert_eq!(nearby(&cache, &aabb(10, 32, 1, 1)), []);
+    }
+
+    #[test]
+    fn cache_removal_clears_buckets() {
+        let mut cache = ColliderCache::default();
+        let (first, second) = (Entity::from_raw(0), Entity::from_raw(1));
+        cache.insert(first, vec![aabb(0, 0, 20, 1)], Default::default());
+        cache.insert(second, vec![aabb(5, 0, 1, 1)], Default::default());
+
+        cache.remove(first);
+        assert_eq!(nearby(&cache, &aabb(0, 0, 32, 1)), [second]);
+
+        // Inserting again replaces the entity's previous AABBs.
+        cache.insert(second, vec![aabb(40, 0, 1, 1)], Default::default());
+        assert_eq!(nearby(&cache, &aabb(0, 0, 16, 1)), []);
+        assert_eq!(nearby(&cache, &aabb(32, 0, 16, 1)), [second]);
+
+        cache.remove(second);
+        assert!(cache.buckets.is_empty());
+        assert!(cache.collisions.is_empty());
+    }
+}
@@ -0,0 +1,306 @@
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityHashMap, EntityHashSet},
+    event::{Event, EventWriter},
+    system::{Query, Res, ResMut, Resource},
+};
+use bevy_math::{IVec2, UVec2};
+use std::collections::HashMap;
+
+use spatial_grid::{grid::PhysicsGridMember, position::Position};
+
+use super::{
+    actor::FilterActors,
+    collision::{Aabb, Collider, ColliderCache, Overlaps},
+    grid::{grid_enabled, grid_of, DisabledGrids},
+};
+
+/// Opts an actor into contacts with other actors. Two actors make contact while touching
+/// if each one's `groups` share a bit with the other's `mask`.
+#[derive(Component, Debug, Clone, Copy)]
+#[require(ActorContacts)]
+pub struct ActorContact {
+    pub groups: u32,
+    pub mask: u32,
+    /// Blocking actors can't move into other blocking actors they make contact with.
+    pub blocking: bool,
+}
+
+impl Default for ActorContact {
+    fn default() -> Self {
+        Self {
+            groups: 1,
+            mask: u32::MAX,
+            blocking: false,
+        }
+    }
+}
+
+impl ActorContact {
+    pub fn interacts(&self, other: &Self) -> bool {
+        self.groups & other.mask != 0 && other.groups & self.mask != 0
+    }
+}
+
+/// Actors currently in contact with this one.
+#[derive(Component, Debug, Default, Deref, DerefMut)]
+pub struct ActorContacts(pub(crate) EntityHashSet);
+
+/// Sent once for each of the two actors, with the other one as `other`.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ActorContactStarted {
+    pub actor: Entity,
+    pub other: Entity,
+}
+
+/// Sent once for each of the two actors, also when `other` is despawned.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ActorContactEnded {
+    pub actor: Entity,
+    pub other: Entity,
+}
+
+/// Actors with an `ActorContact`, bucketed per grid like solids. Rebuilt once per tick
+/// before actors move, `actor_move_system` updates the actors it moves.
+#[derive(Resource, Debug, Default)]
+pub struct ActorContactCache {
+    grids: HashMap<Option<Entity>, ColliderCache>,
+    contacts: EntityHashMap<ActorContact>,
+    empty: ColliderCache,
+}
+
+/// Actors in the same grid that `actor` makes contact with.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ContactQuery<'a> {
+    pub(crate) cache: &'a ColliderCache,
+    contacts: &'a EntityHashMap<ActorContact>,
+    actor: Entity,
+    contact: ActorContact,
+    /// Only blocking actors are included.
+    blocking: bool,
+}
+
+impl ContactQuery<'_> {
+    pub(crate) fn interacts(&self, other: Entity) -> bool {
+        let other_contact = &self.contacts[&other];
+        other != self.actor
+            && (!self.blocking || other_contact.blocking)
+            && self.contact.interacts(other_contact)
+    }
+}
+
+impl ActorContactCache {
+    pub(crate) fn update(
+        &mut self,
+        actor: Entity,
+        member: Option<&PhysicsGridMember>,
+        position: &Position,
+        collider: &Collider,
+    ) {
+        if self.contacts.contains_key(&actor) {
+            self.grids.entry(grid_of(member)).or_default().insert(
+                actor,
+                collider.shape.iter_at(**position).collect(),
+                collider.layers,
+            );
+        }
+    }
+
+    pub(crate) fn query(
+        &self,
+        actor: Entity,
+        member: Option<&PhysicsGridMember>,
+    ) -> Option<ContactQuery<'_>> {
+        let contact = *self.contacts.get(&actor)?;
+        Some(ContactQuery {
+            cache: self.grids.get(&grid_of(member)).unwrap_or(&self.empty),
+            contacts: &self.contacts,
+            actor,
+            contact,
+            blocking: false,
+        })
+    }
+
+    /// Like `query` but only for blocking actors and the blocking actors they make contact with.
+    pub(crate) fn blocking_query(
+        &self,
+        actor: Entity,
+        member: Option<&PhysicsGridMember>,
+    ) -> Option<ContactQuery<'_>> {
+        self.query(actor, member)
+            .filter(|query| query.contact.blocking)
+            .map(|query| ContactQuery {
+                blocking: true,
+                ..query
+            })
+    }
+}
+
+pub(crate) fn update_actor_contact_cache(
+    mut cache: ResMut<ActorContactCache>,
+    q_actors: Query<
+        (
+            Entity,
+            &Position,
+            &Collider,
+            Option<&PhysicsGridMember>,
+            &ActorContact,
+        ),
+        FilterActors,
+    >,
+) {
+    cache.grids.clear();
+    cache.contacts.clear();
+    for (actor, position, collider, member, contact) in q_actors.iter() {
+        cache.contacts.insert(actor, *contact);
+        cache.update(actor, member, position, collider);
+    }
+}
+
+pub(crate) fn update_actor_contacts(
+    mut q_actors: Query<
+        (
+            Entity,
+            &Position,
+            &Collider,
+            Option<&PhysicsGridMember>,
+            &mut ActorContacts,
+        ),
+        FilterActors,
+    >,
+    q_disabled: DisabledGrids,
+    cache: Res<ActorContactCache>,
+    mut started: EventWriter<ActorContactStarted>,
+    mut ended: EventWriter<ActorContactEnded>,
+) {
+    for (actor, position, collider, member, mut contacts) in q_actors.iter_mut() {
+        if !grid_enabled(&q_disabled, grid_of(member)) {
+            continue;
+        }
+        let Some(nearby) = cache.query(actor, member) else {
+            continue;
+        };
+
+        let mut current = EntityHashSet::default();
+        for aabb in collider.shape.iter_at(**position) {
+            for (other, other_aabb) in nearby.cache.nearby(&grown(&aabb, IVec2::ONE)) {
+                if nearby.interacts(other) && touching(&aabb, other_aabb) {
+                    current.insert(other);
+                }
+            }
+        }
+
+        for &other in current.difference(&contacts) {
+            started.send(ActorContactStarted { actor, other });
+        }
+        for &other in contacts.difference(&current) {
+            ended.send(ActorContactEnded { actor, other });
+        }
+        if **contacts != current {
+            **contacts = current;
+        }
+    }
+}
+
+// Overlapping or sharing an edge, corners alone don't count.
+fn touching(a: &Aabb, b: &Aabb) -> bool {
+    grown(a, IVec2::X).overlaps(b) || grown(a, IVec2::Y).overlaps(b)
+}
+
+fn grown(aabb: &Aabb, by: IVec2) -> Aabb {
+    Aabb {
+        start: aabb.start - by,
+        size: aabb.size + UVec2::new(2 * by.x as u32, 2 * by.y as u32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{event::Events, system::RunSystemOnce, world::World};
+    use bevy_math::Vec2;
+
+    use super::*;
+    use crate::{
+        actor::actor_move_system,
+        movement::Movement,
+        test_util::{spawn_actor, world},
+    };
+
+    fn spawn_contact(world: &mut World, x: i32, contact: ActorContact) -> Entity {
+        let actor = spawn_actor(world, IVec2::new(x, 0));
+        world.entity_mut(actor).insert(contact);
+        actor
+    }
+
+    fn update_contacts(world: &mut World) {
+        world.run_system_once(update_actor_contact_cache).unwrap();
+        world.run_system_once(update_actor_contacts).unwrap();
+    }
+
+    #[test]
+    fn blocking_actors_stop_each_other() {
+        let mut world = world();
+        let blocking = ActorContact {
+            blocking: true,
+            ..Default::default()
+        };
+        let actor = spawn_contact(&mut world, 0, blocking);
+        spawn_contact(&mut world, 4, blocking);
+
+        world
+            .get_mut::<Movement>(actor)
+            .unwrap()
+            .add(Vec2::new(6.0, 0.0));
+        world.run_system_once(update_actor_contact_cache).unwrap();
+        world.run_system_once(actor_move_system).unwrap();
+
+        assert_eq!(world.get::<Position>(actor).unwrap().x, 3);
+    }
+
+    #[test]
+    fn blocking_actors_pass_through_non_blocking_ones() {
+        let mut world = world();
+        let blocking = ActorContact {
+            blocking: true,
+            ..Default::default()
+        };
+        let actor = spawn_contact(&mut world, 0, blocking);
+        spawn_contact(&mut world, 4, ActorContact::default());
+
+        world
+            .get_mut::<Movement>(actor)
+            .unwrap()
+            .add(Vec2::new(6.0, 0.0));
+        world.run_system_once(update_actor_contact_cache).unwrap();
+        world.run_system_once(actor_move_system).unwrap();
+
+        assert_eq!(world.get::<Position>(actor).unwrap().x, 6);
+    }
+
+    #[test]
+    fn touching_actors_report_contact_in_matching_groups() {
+        let mut world = world();
+        let actor = spawn_contact(&mut world, 0, ActorContact::default());
+        let other = spawn_contact(&mut world, 1, ActorContact::default());
+        let ignored = spawn_contact(
+            &mut world,
+            -1,
+            ActorContact {
+                groups: 2,
+                mask: 2,
+                ..Default::default()
+            },
+        );
+
+        update_contacts(&mut world);
+        assert_eq!(world.resource::<Events<ActorContactStarted>>().len(), 2);
+        let contacts = world.get::<ActorContacts>(actor).unwrap();
+        assert!(contacts.contains(&other) && !contacts.contains(&ignored));
+
+        world.get_mut::<Position>(other).unwrap().x = 2;
+        update_contacts(&mut world);
+        assert_eq!(world.resource::<Events<ActorContactEnded>>().len(), 2);
+        assert!(world.get::<ActorContacts>(actor).unwrap().is_empty());
+    }
+}
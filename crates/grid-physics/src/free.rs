@@ -1,60 +1,98 @@
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    query::{With, Without},
+    query::{Has, With, Without},
     system::{Commands, Query, Res},
 };
 use bevy_time::Time;
 
-use spatial_grid::{position::Position, remainder::Remainder};
+use spatial_grid::{
+    grid::{PhysicsGridMember, SpatialGrid},
+    position::Position,
+    remainder::Remainder,
+};
 
-use crate::actor::FilterActors;
+use crate::actor::{FilterActors, StepHeight};
 
 use super::{
     actor::Actor,
-    collision::Collider,
-    gravity::{Gravity, GravityResource},
+    collision::{Collider, CollisionFilter},
+    contact::ActorContactCache,
+    gravity::{Gravity, GravityResource, GridGravity},
+    grid::{grid_enabled, grid_of, DisabledGrids},
     movement::{Movement, MovementObstructed},
-    solid::{FilterSolids, RidingEntities, SolidCollisionCache},
+    one_way::DropThrough,
+    solid::{FilterSolids, RidingEntities, SolidCollisionCaches},
     velocity::Velocity,
 };
 
+type ObstructedActorQueryData<'a> = (
+    Entity,
+    &'a mut MovementObstructed,
+    &'a Position,
+    &'a Collider,
+    Option<&'a PhysicsGridMember>,
+    Has<DropThrough>,
+    Option<&'a StepHeight>,
+);
+
 pub fn update_obstructions(
-    mut q_actors: Query<
-        (&mut MovementObstructed, &Position, &Collider),
-        (FilterActors, With<FreeMarker>),
-    >,
-    solid_collision_cache: Res<SolidCollisionCache>,
+    mut q_actors: Query<ObstructedActorQueryData, (FilterActors, With<FreeMarker>)>,
+    q_disabled: DisabledGrids,
+    solid_collision_caches: Res<SolidCollisionCaches>,
+    contact_cache: Res<ActorContactCache>,
 ) {
-    for (mut obstructed, actor_position, actor_collider) in q_actors.iter_mut() {
+    for (
+        actor,
+        mut obstructed,
+        actor_position,
+        actor_collider,
+        member,
+        drop_through,
+        step_height,
+    ) in q_actors.iter_mut()
+    {
+        if !grid_enabled(&q_disabled, grid_of(member)) {
+            continue;
+        }
+        let solid_collision_cache = solid_collision_caches.get(grid_of(member));
+        let filter = CollisionFilter::new(drop_through)
+            .with_actors(contact_cache.blocking_query(actor, member));
+        let step_height = step_height.copied().unwrap_or_default();
         *obstructed = MovementObstructed {
             x: Actor::test_move_x(
                 1.0,
                 actor_position,
                 &Remainder::default(),
                 actor_collider,
-                solid_collision_cache.as_ref(),
+                solid_collision_cache,
+                filter,
+                step_height,
             ),
             y: Actor::test_move_y(
                 1.0,
                 actor_position,
                 &Remainder::default(),
                 actor_collider,
-                solid_collision_cache.as_ref(),
+                solid_collision_cache,
+                filter,
             ),
             neg_x: Actor::test_move_x(
                 -1.0,
                 actor_position,
                 &Remainder::default(),
                 actor_collider,
-                solid_collision_cache.as_ref(),
+                solid_collision_cache,
+                filter,
+                step_height,
             ),
             neg_y: Actor::test_move_y(
                 -1.0,
                 actor_position,
                 &Remainder::default(),
                 actor_collider,
-                solid_collision_cache.as_ref(),
+                solid_collision_cache,
+                filter,
             ),
         };
     }
@@ -83,21 +121,40 @@ pub(super) fn obstruct_velocity(
 }
 
 pub(super) fn apply_velocity_to_free(
-    mut q_free_actors: Query<(&mut Movement, &Velocity), With<FreeMarker>>,
+    mut q_free_actors: Query<
+        (&mut Movement, &Velocity, Option<&PhysicsGridMember>),
+        With<FreeMarker>,
+    >,
+    q_disabled: DisabledGrids,
     time: Res<Time>,
 ) {
-    for (mut actor_movement, actor_velocity) in q_free_actors.iter_mut() {
+    for (mut actor_movement, actor_velocity, member) in q_free_actors.iter_mut() {
+        if !grid_enabled(&q_disabled, grid_of(member)) {
+            continue;
+        }
         actor_movement.add(**actor_velocity * time.delta_secs());
     }
 }
 
 pub(super) fn apply_gravity_to_free(
-    mut q_free_actors: Query<(&mut Velocity, &Gravity), With<FreeMarker>>,
+    mut q_free_actors: Query<
+        (&mut Velocity, &Gravity, Option<&PhysicsGridMember>),
+        With<FreeMarker>,
+    >,
+    q_grids: Query<Option<&GridGravity>, With<SpatialGrid>>,
+    q_disabled: DisabledGrids,
     res_gravity: Res<GravityResource>,
     time: Res<Time>,
 ) {
-    for (mut actor_velocity, actor_gravity) in q_free_actors.iter_mut() {
-        actor_velocity.y += res_gravity.acceleration * actor_gravity.multiplier * time.delta_secs();
+    for (mut actor_velocity, actor_gravity, member) in q_free_actors.iter_mut() {
+        let grid = grid_of(member);
+        if !grid_enabled(&q_disabled, grid) {
+            continue;
+        }
+        let acceleration = grid
+            .and_then(|grid| q_grids.get(grid).ok().flatten())
+            .map_or(res_gravity.acceleration, |gravity| gravity.acceleration);
+        actor_velocity.y += acceleration * actor_gravity.multiplier * time.delta_secs();
     }
 }
 
@@ -107,22 +164,44 @@ pub struct FreeGrounded;
 #[derive(Debug, Component, Default)]
 pub(crate) struct FreeAirborne;
 
+type FreeActorQueryData<'a> = (
+    Entity,
+    &'a Position,
+    &'a Remainder,
+    &'a Velocity,
+    &'a Collider,
+    Option<&'a PhysicsGridMember>,
+    Has<DropThrough>,
+);
+
 pub(super) fn update_free_actor_state(
     mut commands: Commands,
     mut q_solids: Query<&mut RidingEntities, FilterSolids>,
-    q_free_actors: Query<(Entity, &Position, &Remainder, &Velocity, &Collider), With<FreeMarker>>,
+    q_free_actors: Query<FreeActorQueryData, With<FreeMarker>>,
     q_grounded_extra: Query<Entity, (Without<FreeMarker>, With<FreeGrounded>)>,
-    solid_collision_cache: Res<SolidCollisionCache>,
+    q_disabled: DisabledGrids,
+    solid_collision_caches: Res<SolidCollisionCaches>,
 ) {
-    for (actor, position, remainder, velocity, collider) in q_free_actors.iter() {
-        for mut riding in q_solids.iter_mut() {
-            riding.clear();
-        }
+    for mut riding in q_solids.iter_mut() {
+        riding.clear();
+    }
 
+    for (actor, position, remainder, velocity, collider, member, drop_through) in
+        q_free_actors.iter()
+    {
+        if !grid_enabled(&q_disabled, grid_of(member)) {
+            continue;
+        }
+        let solid_collision_cache = solid_collision_caches.get(grid_of(member));
         if velocity.y <= 0.0 {
-            if let Some(solid) =
-                Actor::test_move_y(-1.0, position, remainder, collider, &solid_collision_cache)
-            {
+            if let Some(solid) = Actor::test_move_y(
+                -1.0,
+                position,
+                remainder,
+                collider,
+                solid_collision_cache,
+                CollisionFilter::new(drop_through),
+            ) {
                 commands
                     .entity(actor)
                     .insert(FreeGrounded)
@@ -10,6 +10,12 @@ impl Default for Gravity {
     }
 }
 
+/// Overrides `GravityResource` for every member of a grid, insert on the `SpatialGrid` entity.
+#[derive(Component, Debug, Clone)]
+pub struct GridGravity {
+    pub acceleration: f32,
+}
+
 #[derive(Resource)]
 pub(crate) struct GravityResource {
     pub(crate) acceleration: f32,
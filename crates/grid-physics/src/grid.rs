@@ -0,0 +1,17 @@
+use bevy_ecs::{component::Component, entity::Entity, query::With, system::Query};
+use spatial_grid::grid::PhysicsGridMember;
+
+/// Pauses physics for every member of a grid, insert on the `SpatialGrid` entity.
+#[derive(Component, Debug, Default, Clone)]
+pub struct PhysicsGridDisabled;
+
+pub(crate) type DisabledGrids<'w, 's> = Query<'w, 's, (), With<PhysicsGridDisabled>>;
+
+/// Entities without a `PhysicsGridMember` share the `None` grid.
+pub(crate) fn grid_of(member: Option<&PhysicsGridMember>) -> Option<Entity> {
+    member.map(|member| member.grid)
+}
+
+pub(crate) fn grid_enabled(disabled: &DisabledGrids, grid: Option<Entity>) -> bool {
+    !grid.is_some_and(|grid| disabled.contains(grid))
+}
@@ -0,0 +1,92 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::With,
+    system::{Query, Res},
+};
+use bevy_hierarchy::{Children, HierarchyQueryExt};
+use bevy_math::{IVec2, Vec2};
+use bevy_time::{Fixed, Time};
+use spatial_grid::{global_position::GlobalPosition, position::Position, remainder::Remainder};
+
+use super::movement::Movement;
+
+/// Position at the start of the last physics tick, `None` until the first tick.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct PreviousPosition(pub(crate) Option<Vec2>);
+
+impl PreviousPosition {
+    /// The cell drawn `alpha` of the way from the last tick to the current one,
+    /// see `Time::<Fixed>::overstep_fraction`.
+    pub fn interpolate(&self, position: &Position, remainder: &Remainder, alpha: f32) -> IVec2 {
+        let Some(previous) = self.0 else {
+            return **position;
+        };
+        let current = position.as_vec2() + **remainder;
+        previous.lerp(current, alpha).round().as_ivec2()
+    }
+}
+
+pub(crate) fn store_previous_positions(
+    mut q_moving: Query<(&Position, &Remainder, &mut PreviousPosition), With<Movement>>,
+) {
+    for (position, remainder, mut previous) in q_moving.iter_mut() {
+        previous.0 = Some(position.as_vec2() + **remainder);
+    }
+}
+
+/// Renders moving entities between their last two physics ticks, so motion stays smooth
+/// when frames don't line up with ticks. Only `GlobalPosition` is offset, `Position` is
+/// left to physics. Descendants are offset along with the entity so they don't lag behind.
+pub(crate) fn interpolate_positions(
+    q_moving: Query<(Entity, &Position, &Remainder, &PreviousPosition), With<Movement>>,
+    q_children: Query<&Children>,
+    mut q_global_position: Query<&mut GlobalPosition>,
+    time: Res<Time<Fixed>>,
+) {
+    let alpha = time.overstep_fraction();
+    for (entity, position, remainder, previous) in q_moving.iter() {
+        let offset = previous.interpolate(position, remainder, alpha) - **position;
+        if offset == IVec2::ZERO {
+            continue;
+        }
+        for entity in std::iter::once(entity).chain(q_children.iter_descendants(entity)) {
+            if let Ok(mut global_position) = q_global_position.get_mut(entity) {
+                **global_position += offset;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_hierarchy::BuildChildren;
+
+    use super::*;
+
+    #[test]
+    fn children_follow_the_interpolated_parent() {
+        // Without overstep entities are drawn where the last tick started.
+        let mut world = World::new();
+        world.init_resource::<Time<Fixed>>();
+
+        let parent = world
+            .spawn((
+                Movement::default(),
+                Position(IVec2::new(10, 0)),
+                Remainder::default(),
+                PreviousPosition(Some(Vec2::ZERO)),
+                GlobalPosition(IVec2::new(10, 0)),
+            ))
+            .id();
+        let child = world
+            .spawn((Position(IVec2::Y), GlobalPosition(IVec2::new(10, 1))))
+            .set_parent(parent)
+            .id();
+
+        world.run_system_once(interpolate_positions).unwrap();
+        assert_eq!(**world.get::<GlobalPosition>(parent).unwrap(), IVec2::ZERO);
+        assert_eq!(**world.get::<GlobalPosition>(child).unwrap(), IVec2::Y);
+    }
+}
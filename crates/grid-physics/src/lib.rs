@@ -1,14 +1,24 @@
 pub mod actor;
+pub mod one_way;
+pub mod sensor;
 pub mod solid;
+pub mod spatial_query;
+pub mod squish;
 
 pub mod collision;
+pub mod contact;
 
 pub mod gravity;
 pub mod movement;
 pub mod velocity;
 
 pub mod free;
+pub mod grid;
+pub mod interpolation;
 
 pub mod plugin;
 
 pub mod sets;
+
+#[cfg(test)]
+mod test_util;
@@ -1,7 +1,10 @@
 use bevy_ecs::{component::Component, entity::Entity};
 use bevy_math::Vec2;
 
+use super::interpolation::PreviousPosition;
+
 #[derive(Component, Default, Debug, Clone)]
+#[require(PreviousPosition)]
 pub struct Movement {
     pub(crate) delta: Vec2,
 }
@@ -0,0 +1,38 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    system::{Commands, Query, Res},
+};
+use bevy_time::Time;
+use spatial_grid::direction::Direction;
+
+/// Solids actors can pass through moving in the given direction, they only block
+/// actors moving the opposite way that aren't already inside them.
+/// `OneWay(Direction::PosY)` is a platform actors jump up through and land on.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct OneWay(pub Direction);
+
+/// Lets an actor fall through one-way solids until the timer runs out.
+#[derive(Component, Debug, Clone)]
+pub struct DropThrough {
+    pub timer: f32,
+}
+
+impl Default for DropThrough {
+    fn default() -> Self {
+        Self { timer: 0.25 }
+    }
+}
+
+pub(crate) fn update_drop_through(
+    mut commands: Commands,
+    mut q_actors: Query<(Entity, &mut DropThrough)>,
+    time: Res<Time>,
+) {
+    for (actor, mut drop_through) in q_actors.iter_mut() {
+        drop_through.timer -= time.delta_secs();
+        if drop_through.timer <= 0.0 {
+            commands.entity(actor).remove::<DropThrough>();
+        }
+    }
+}
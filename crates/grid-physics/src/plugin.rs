@@ -1,5 +1,6 @@
-use bevy_app::{Plugin, PostUpdate};
+use bevy_app::{FixedUpdate, Plugin, PostUpdate};
 use bevy_ecs::schedule::{IntoSystemConfigs, SystemSet};
+use bevy_time::{Fixed, Time};
 use bevy_transform::TransformSystem;
 
 use crate::{
@@ -9,15 +10,32 @@ use crate::{
 
 use super::{
     actor::actor_move_system,
+    contact::{
+        update_actor_contact_cache, update_actor_contacts, ActorContactCache, ActorContactEnded,
+        ActorContactStarted,
+    },
     free::{
         apply_gravity_to_free, apply_velocity_to_free, obstruct_velocity, update_free_actor_state,
     },
     gravity::GravityResource,
-    solid::{solid_move_system, update_collision_cache, SolidCollisionCache},
+    interpolation::{interpolate_positions, store_previous_positions},
+    one_way::update_drop_through,
+    sensor::{update_sensors, TriggerEntered, TriggerExited},
+    solid::{solid_move_system, update_collision_cache, SolidCollisionCaches},
+    squish::{resolve_squished_actors, ActorSquished},
 };
 
-#[derive(Default)]
-pub struct PhysicsPlugin;
+/// Runs physics in `FixedUpdate`, so results don't depend on the frame rate.
+pub struct PhysicsPlugin {
+    /// Physics ticks per second.
+    pub tick_rate: f64,
+}
+
+impl Default for PhysicsPlugin {
+    fn default() -> Self {
+        Self { tick_rate: 60.0 }
+    }
+}
 
 #[derive(SystemSet, Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
 
@@ -30,15 +48,28 @@ pub enum PhysicsUpdateSet {
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut bevy_app::App) {
         app.init_resource::<GravityResource>()
-            .init_resource::<SolidCollisionCache>()
+            .init_resource::<SolidCollisionCaches>()
+            .init_resource::<ActorContactCache>()
             .init_resource::<EnablePhysicsSystems>()
+            .add_event::<ActorSquished>()
+            .add_event::<TriggerEntered>()
+            .add_event::<TriggerExited>()
+            .add_event::<ActorContactStarted>()
+            .add_event::<ActorContactEnded>()
+            .insert_resource(Time::<Fixed>::from_hz(self.tick_rate))
             .add_systems(
-                PostUpdate,
+                FixedUpdate,
                 (
+                    store_previous_positions,
                     update_collision_cache,
+                    update_drop_through,
                     update_free_actor_state,
                     solid_move_system,
+                    resolve_squished_actors,
+                    update_actor_contact_cache,
                     actor_move_system,
+                    update_sensors,
+                    update_actor_contacts,
                     update_obstructions,
                     obstruct_velocity,
                     apply_velocity_to_free,
@@ -46,10 +77,87 @@ impl Plugin for PhysicsPlugin {
                 )
                     .chain()
                     .run_if(physics_systems_enabled)
-                    .before(TransformSystem::TransformPropagate)
                     .in_set(PhysicsUpdateSet::PostUpdate)
                     .before(PhysicsUpdateSet::Update)
                     .after(PhysicsUpdateSet::PreUpdate),
+            )
+            .add_systems(
+                PostUpdate,
+                interpolate_positions
+                    .run_if(physics_systems_enabled)
+                    .after(TransformSystem::TransformPropagate),
             );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy_app::App;
+    use bevy_math::{IVec2, UVec2, Vec2};
+    use bevy_time::{TimePlugin, TimeUpdateStrategy};
+    use spatial_grid::position::Position;
+
+    use super::*;
+    use crate::{
+        actor::ActorPhysicsBundle,
+        collision::Aabb,
+        free::FreeMarker,
+        gravity::Gravity,
+        solid::SolidPhysicsBundle,
+        velocity::Velocity,
+    };
+
+    // Runs one second of a jump with frames of `ticks_per_frame` physics ticks.
+    fn simulate(ticks_per_frame: f64) -> (IVec2, Vec2) {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, PhysicsPlugin::default()));
+        let timestep = app.world().resource::<Time<Fixed>>().timestep();
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(
+            timestep.mul_f64(ticks_per_frame),
+        ));
+
+        app.world_mut().spawn(SolidPhysicsBundle {
+            collider: Aabb {
+                start: IVec2::ZERO,
+                size: UVec2::new(32, 1),
+            }
+            .into(),
+            ..Default::default()
+        });
+        let actor = app
+            .world_mut()
+            .spawn((
+                ActorPhysicsBundle {
+                    position: IVec2::new(4, 1).into(),
+                    collider: Aabb {
+                        start: IVec2::ZERO,
+                        size: UVec2::new(1, 2),
+                    }
+                    .into(),
+                    ..Default::default()
+                },
+                FreeMarker,
+                Gravity::default(),
+                Velocity::new(13.0, 47.0),
+            ))
+            .id();
+
+        while app.world().resource::<Time<Fixed>>().elapsed() < Duration::from_secs(1) {
+            app.update();
+        }
+        let world = app.world();
+        (
+            **world.get::<Position>(actor).unwrap(),
+            **world.get::<Velocity>(actor).unwrap(),
+        )
+    }
+
+    #[test]
+    fn results_do_not_depend_on_frame_rate() {
+        let result = simulate(2.0);
+        assert_ne!(result.0, IVec2::new(4, 1));
+        assert_eq!(result, simulate(0.5));
+    }
+}
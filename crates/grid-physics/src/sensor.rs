@@ -0,0 +1,161 @@
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    entity::{Entity, EntityHashSet},
+    event::{Event, EventWriter},
+    query::{With, Without},
+    system::Query,
+};
+use spatial_grid::{
+    grid::PhysicsGridMember,
+    position::{Position, SpatialBundle},
+};
+
+use super::{
+    actor::{Actor, FilterActors},
+    collision::{Aabb, Collider, Overlaps},
+    grid::{grid_enabled, grid_of, DisabledGrids},
+    solid::Solid,
+};
+
+/// A collider that doesn't block movement but reports actors overlapping it.
+#[derive(Component, Debug, Default, Clone)]
+pub struct Sensor;
+
+/// Actors currently overlapping the sensor.
+#[derive(Component, Debug, Default, Deref, DerefMut)]
+pub struct SensorOverlaps(pub(crate) EntityHashSet);
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TriggerEntered {
+    pub sensor: Entity,
+    pub actor: Entity,
+}
+
+/// Also sent when the actor or its collider is removed.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TriggerExited {
+    pub sensor: Entity,
+    pub actor: Entity,
+}
+
+#[derive(Bundle, Default)]
+pub struct SensorBundle {
+    pub sensor: Sensor,
+    pub position: SpatialBundle,
+    pub collider: Collider,
+    pub overlaps: SensorOverlaps,
+}
+
+pub(crate) type FilterSensors = (With<Sensor>, Without<Actor>, Without<Solid>);
+
+pub(crate) fn update_sensors(
+    mut q_sensors: Query<
+        (
+            Entity,
+            &Position,
+            &Collider,
+            &mut SensorOverlaps,
+            Option<&PhysicsGridMember>,
+        ),
+        FilterSensors,
+    >,
+    q_actors: Query<(Entity, &Position, &Collider, Option<&PhysicsGridMember>), FilterActors>,
+    q_disabled: DisabledGrids,
+    mut entered: EventWriter<TriggerEntered>,
+    mut exited: EventWriter<TriggerExited>,
+) {
+    for (sensor, position, collider, mut overlaps, member) in q_sensors.iter_mut() {
+        let grid = grid_of(member);
+        if !grid_enabled(&q_disabled, grid) {
+            continue;
+        }
+        let sensor_aabbs: Vec<Aabb> = collider.shape.iter_at(**position).collect();
+
+        let current: EntityHashSet = q_actors
+            .iter()
+            .filter(|(_, _, _, actor_member)| grid_of(*actor_member) == grid)
+            .filter(|(_, actor_position, actor_collider, _)| {
+                actor_collider
+                    .shape
+                    .iter_at(***actor_position)
+                    .any(|aabb| sensor_aabbs.iter().any(|sensor| aabb.overlaps(sensor)))
+            })
+            .map(|(actor, ..)| actor)
+            .collect();
+
+        for &actor in current.difference(&overlaps) {
+            entered.send(TriggerEntered { sensor, actor });
+        }
+        for &actor in overlaps.difference(&current) {
+            exited.send(TriggerExited { sensor, actor });
+        }
+        if **overlaps != current {
+            **overlaps = current;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{event::Events, system::RunSystemOnce};
+    use bevy_math::{IVec2, UVec2};
+
+    use super::*;
+    use crate::{
+        grid::PhysicsGridDisabled,
+        test_util::{collider, spawn_actor, world},
+    };
+
+    #[test]
+    fn sensor_reports_actor_entering_and_exiting() {
+        let mut world = world();
+        let sensor = world
+            .spawn((
+                Sensor,
+                Position(IVec2::new(4, 0)),
+                collider(UVec2::new(2, 2)),
+                SensorOverlaps::default(),
+            ))
+            .id();
+        let actor = spawn_actor(&mut world, IVec2::ZERO);
+
+        world.run_system_once(update_sensors).unwrap();
+        assert!(world.resource::<Events<TriggerEntered>>().is_empty());
+
+        world.get_mut::<Position>(actor).unwrap().x = 5;
+        world.run_system_once(update_sensors).unwrap();
+        assert_eq!(world.resource::<Events<TriggerEntered>>().len(), 1);
+        assert!(world
+            .get::<SensorOverlaps>(sensor)
+            .unwrap()
+            .contains(&actor));
+
+        world.get_mut::<Position>(actor).unwrap().x = 6;
+        world.run_system_once(update_sensors).unwrap();
+        assert_eq!(world.resource::<Events<TriggerExited>>().len(), 1);
+        assert!(world.get::<SensorOverlaps>(sensor).unwrap().is_empty());
+    }
+
+    #[test]
+    fn sensors_in_disabled_grids_keep_their_overlaps() {
+        let mut world = world();
+        let grid = world.spawn(PhysicsGridDisabled).id();
+        let sensor = world
+            .spawn((
+                Sensor,
+                Position(IVec2::ZERO),
+                collider(UVec2::new(2, 2)),
+                SensorOverlaps::default(),
+                PhysicsGridMember { grid },
+            ))
+            .id();
+        let actor = spawn_actor(&mut world, IVec2::ZERO);
+        world.entity_mut(actor).insert(PhysicsGridMember { grid });
+
+        world.run_system_once(update_sensors).unwrap();
+        assert!(world.resource::<Events<TriggerEntered>>().is_empty());
+        assert!(world.get::<SensorOverlaps>(sensor).unwrap().is_empty());
+    }
+}
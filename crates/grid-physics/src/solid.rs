@@ -1,23 +1,34 @@
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     bundle::Bundle,
+    change_detection::DetectChanges,
     component::Component,
     entity::{Entity, EntityHashMap, EntityHashSet},
-    query::{With, Without},
+    event::EventWriter,
+    query::{Has, With, Without},
+    removal_detection::RemovedComponents,
     system::{Commands, Query, ResMut, Resource},
+    world::Ref,
 };
-use bevy_math::Vec2;
+use bevy_math::{IVec2, Vec2};
+use std::collections::HashMap;
 
 use spatial_grid::{
     direction::Direction,
+    grid::PhysicsGridMember,
     position::{Position, SpatialBundle},
     remainder::Remainder,
 };
 
-use crate::collision::Aabb;
+use crate::{
+    collision::{Aabb, ColliderCache, CollisionFilter, CollisionLayers},
+    grid::{grid_enabled, grid_of, DisabledGrids},
+    one_way::{DropThrough, OneWay},
+    squish::{ActorSquished, SquishedMarker},
+};
 
 use super::{
-    actor::{Actor, FilterActors},
+    actor::{Actor, FilterActors, StepHeight},
     collision::Collider,
     movement::Movement,
 };
@@ -30,92 +41,161 @@ pub struct RidingEntities(pub(crate) EntityHashSet);
 
 pub(crate) type FilterSolids = (With<Solid>, Without<Actor>);
 
+type MovingSolidQueryData<'a> = (
+    Entity,
+    &'a mut Position,
+    &'a mut Remainder,
+    &'a Collider,
+    &'a mut Movement,
+    &'a RidingEntities,
+    Option<&'a PhysicsGridMember>,
+    Option<&'a OneWay>,
+);
+
+type CarriedActorQueryData<'a> = (
+    Entity,
+    &'a mut Position,
+    &'a mut Remainder,
+    &'a Collider,
+    Option<&'a PhysicsGridMember>,
+    Has<DropThrough>,
+);
+
 pub(crate) fn solid_move_system(
     mut commands: Commands,
-    mut q_solids: Query<
-        (
-            Entity,
-            &mut Position,
-            &mut Remainder,
-            &Collider,
-            &mut Movement,
-            &RidingEntities,
-        ),
-        FilterSolids,
-    >,
-    mut q_actors: Query<(Entity, &mut Position, &mut Remainder, &Collider), FilterActors>,
-    mut solid_collision_cache: ResMut<SolidCollisionCache>,
+    mut q_solids: Query<MovingSolidQueryData, FilterSolids>,
+    mut q_actors: Query<CarriedActorQueryData, FilterActors>,
+    q_disabled: DisabledGrids,
+    mut solid_collision_caches: ResMut<SolidCollisionCaches>,
+    mut squished: EventWriter<ActorSquished>,
 ) {
-    for (solid, mut solid_position, mut solid_remainder, solid_collision, mut movement, riding) in
-        q_solids.iter_mut()
+    for (
+        solid,
+        mut solid_position,
+        mut solid_remainder,
+        solid_collision,
+        mut movement,
+        riding,
+        member,
+        one_way,
+    ) in q_solids.iter_mut()
     {
+        let grid = grid_of(member);
+        if !grid_enabled(&q_disabled, grid) {
+            continue;
+        }
+        let solid_collision_cache = solid_collision_caches.get_mut(grid);
+
         **solid_remainder += movement.delta;
         movement.delta = Vec2::ZERO;
         let movement = solid_remainder.round().as_ivec2();
-        solid_collision_cache.collisions.remove(&solid);
-
-        if movement.x != 0 {
-            solid_remainder.x -= movement.x as f32;
-            solid_position.x += movement.x;
-
-            let solid_aabbs: Vec<Aabb> = solid_collision.shape.iter_at(**solid_position).collect();
-
-            for (actor, mut actor_position, mut actor_remainder, actor_collision) in
-                q_actors.iter_mut()
-            {
-                if let Some(distance) = actor_collision.overlap_distance(
-                    **actor_position,
-                    &solid_aabbs,
-                    if movement.x > 0 {
-                        Direction::PosX
-                    } else {
-                        Direction::NegX
-                    },
-                ) {
-                    let distance = distance * movement.x.signum();
-                    if Actor::move_x(
-                        distance as f32,
-                        &mut actor_position,
-                        &mut actor_remainder,
-                        actor_collision,
-                        &solid_collision_cache,
-                    )
-                    .is_some()
+        if movement == IVec2::ZERO {
+            continue;
+        }
+        solid_collision_cache.remove(solid);
+
+        for axis in [IVec2::X, IVec2::Y] {
+            let amount = movement.dot(axis);
+            if amount == 0 {
+                continue;
+            }
+            **solid_remainder -= (axis * amount).as_vec2();
+
+            let step = amount.signum();
+            let direction = match (axis == IVec2::X, step > 0) {
+                (true, true) => Direction::PosX,
+                (true, false) => Direction::NegX,
+                (false, true) => Direction::PosY,
+                (false, false) => Direction::NegY,
+            };
+            // Move one cell at a time so fast solids can't skip over actors.
+            for _ in 0..amount.abs() {
+                let previous_aabbs: Vec<Aabb> =
+                    solid_collision.shape.iter_at(**solid_position).collect();
+                **solid_position += axis * step;
+                let solid_aabbs: Vec<Aabb> =
+                    solid_collision.shape.iter_at(**solid_position).collect();
+
+                for (
+                    actor,
+                    mut actor_position,
+                    mut actor_remainder,
+                    actor_collision,
+                    actor_member,
+                    drop_through,
+                ) in q_actors.iter_mut()
+                {
+                    if grid_of(actor_member) != grid
+                        || !solid_collision.layers.interacts(&actor_collision.layers)
                     {
-                        commands.entity(actor).insert(SquishedMarker);
+                        continue;
                     }
-                } else if riding.contains(&actor) {
-                    if Actor::move_x(
-                        movement.x as f32,
-                        &mut actor_position,
-                        &mut actor_remainder,
-                        actor_collision,
-                        &solid_collision_cache,
-                    )
-                    .is_some()
-                    {
-                        commands.entity(actor).insert(SquishedMarker);
+
+                    // One-way solids only push actors they run into moving in their direction.
+                    let pushes = one_way.is_none_or(|OneWay(pass)| {
+                        !drop_through
+                            && pass.as_ivec2() == axis * step
+                            && actor_collision
+                                .overlap_distance(**actor_position, &previous_aabbs, direction)
+                                .is_none()
+                    });
+
+                    // Overlapping actors are pushed out of the solid, riders are carried along.
+                    let distance = pushes
+                        .then(|| {
+                            actor_collision.overlap_distance(
+                                **actor_position,
+                                &solid_aabbs,
+                                direction,
+                            )
+                        })
+                        .flatten()
+                        .map(|distance| distance * step)
+                        .or_else(|| riding.contains(&actor).then_some(step));
+                    let Some(distance) = distance else {
+                        continue;
+                    };
+
+                    // Pushed actors don't climb ledges.
+                    let filter = CollisionFilter::new(drop_through);
+                    let blocked = if axis == IVec2::X {
+                        Actor::move_x(
+                            distance as f32,
+                            &mut actor_position,
+                            &mut actor_remainder,
+                            actor_collision,
+                            solid_collision_cache,
+                            filter,
+                            StepHeight::default(),
+                        )
+                    } else {
+                        Actor::move_y(
+                            distance as f32,
+                            &mut actor_position,
+                            &mut actor_remainder,
+                            actor_collision,
+                            solid_collision_cache,
+                            filter,
+                        )
+                    };
+                    if blocked.is_some() {
+                        commands.entity(actor).insert(SquishedMarker { solid });
+                        squished.send(ActorSquished { actor, solid });
                     }
                 }
             }
         }
 
-        if movement.y != 0 {
-            solid_remainder.y -= movement.y as f32;
-            solid_position.y += movement.y;
-        }
-
         // Update collision cache entry
-        {
-            let solid_aabbs: Vec<Aabb> = solid_collision.shape.iter_at(**solid_position).collect();
-            solid_collision_cache.collisions.insert(solid, solid_aabbs);
-        }
+        solid_collision_cache.insert(
+            solid,
+            solid_collision.shape.iter_at(**solid_position).collect(),
+            solid_collision.layers,
+            one_way,
+        );
     }
 }
 
-#[derive(Component)]
-pub(crate) struct SquishedMarker;
-
 #[derive(Bundle, Default)]
 pub struct SolidPhysicsBundle {
     pub solid: Solid,
@@ -124,19 +204,322 @@ pub struct SolidPhysicsBundle {
     pub riding: RidingEntities,
 }
 
-#[derive(Resource, Debug, Default)]
+/// Solid colliders along with the direction of one-way solids.
+#[derive(Debug, Default, Deref)]
 pub struct SolidCollisionCache {
-    pub(crate) collisions: EntityHashMap<Vec<Aabb>>,
+    #[deref]
+    colliders: ColliderCache,
+    one_way: EntityHashMap<Direction>,
+}
+
+impl SolidCollisionCache {
+    pub(crate) fn insert(
+        &mut self,
+        solid: Entity,
+        aabbs: Vec<Aabb>,
+        layers: CollisionLayers,
+        one_way: Option<&OneWay>,
+    ) {
+        self.remove(solid);
+        if let Some(OneWay(direction)) = one_way {
+            self.one_way.insert(solid, *direction);
+        }
+        self.colliders.insert(solid, aabbs, layers);
+    }
+
+    pub(crate) fn remove(&mut self, solid: Entity) {
+        self.one_way.remove(&solid);
+        self.colliders.remove(solid);
+    }
+
+    pub(crate) fn one_way(&self, solid: Entity) -> Option<Direction> {
+        self.one_way.get(&solid).copied()
+    }
+}
+
+/// One `SolidCollisionCache` per physics grid, actors only collide with solids in their own grid.
+#[derive(Resource, Debug, Default)]
+pub struct SolidCollisionCaches {
+    grids: HashMap<Option<Entity>, SolidCollisionCache>,
+    empty: SolidCollisionCache,
+}
+
+impl SolidCollisionCaches {
+    pub fn get(&self, grid: Option<Entity>) -> &SolidCollisionCache {
+        self.grids.get(&grid).unwrap_or(&self.empty)
+    }
+
+    pub(crate) fn get_mut(&mut self, grid: Option<Entity>) -> &mut SolidCollisionCache {
+        self.grids.entry(grid).or_default()
+    }
+
+    pub(crate) fn remove(&mut self, solid: Entity) {
+        for cache in self.grids.values_mut() {
+            cache.remove(solid);
+        }
+    }
 }
 
+type CachedSolidQueryData<'a> = (
+    Entity,
+    Ref<'a, Position>,
+    Ref<'a, Collider>,
+    Option<Ref<'a, PhysicsGridMember>>,
+    Option<Ref<'a, OneWay>>,
+);
+
 pub(crate) fn update_collision_cache(
-    mut solid_collision_cache: ResMut<SolidCollisionCache>,
-    q_solids: Query<(Entity, &Position, &Collider), FilterSolids>,
+    mut solid_collision_caches: ResMut<SolidCollisionCaches>,
+    q_solids: Query<CachedSolidQueryData, FilterSolids>,
+    mut removed_solids: RemovedComponents<Solid>,
+    mut removed_colliders: RemovedComponents<Collider>,
+    mut removed_members: RemovedComponents<PhysicsGridMember>,
+    mut removed_one_way: RemovedComponents<OneWay>,
 ) {
-    solid_collision_cache.collisions.clear();
-    for (solid, position, collider) in q_solids.iter() {
-        solid_collision_cache
-            .collisions
-            .insert(solid, collider.shape.iter_at(**position).collect());
+    for solid in removed_solids.read().chain(removed_colliders.read()) {
+        solid_collision_caches.remove(solid);
+    }
+    let removed: EntityHashSet = removed_members
+        .read()
+        .chain(removed_one_way.read())
+        .collect();
+
+    for (solid, position, collider, member, one_way) in q_solids.iter() {
+        let changed = position.is_changed()
+            || collider.is_changed()
+            || member.as_ref().is_some_and(|member| member.is_changed())
+            || one_way.as_ref().is_some_and(|one_way| one_way.is_changed())
+            || removed.contains(&solid);
+        if !changed {
+            continue;
+        }
+        solid_collision_caches.remove(solid);
+        solid_collision_caches
+            .get_mut(grid_of(member.as_deref()))
+            .insert(
+                solid,
+                collider.shape.iter_at(**position).collect(),
+                collider.layers,
+                one_way.as_deref(),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{event::Events, system::RunSystemOnce, world::World};
+    use bevy_math::{IVec2, UVec2, Vec2};
+
+    use super::*;
+    use crate::{
+        actor::{actor_move_system, StepHeight},
+        test_util::{position, spawn_actor, spawn_solid, world},
+    };
+
+    fn move_actor(world: &mut World, actor: Entity, delta: Vec2) {
+        world.get_mut::<Movement>(actor).unwrap().add(delta);
+        world.run_system_once(update_collision_cache).unwrap();
+        world.run_system_once(actor_move_system).unwrap();
+    }
+
+    fn move_solid(world: &mut World, solid: Entity, delta: Vec2) {
+        world.get_mut::<Movement>(solid).unwrap().add(delta);
+        world.run_system_once(update_collision_cache).unwrap();
+        world.run_system_once(solid_move_system).unwrap();
+    }
+
+    #[test]
+    fn rising_platform_pushes_actor() {
+        let mut world = world();
+        let platform = spawn_solid(&mut world, IVec2::ZERO, UVec2::new(4, 1));
+        let actor = spawn_actor(&mut world, IVec2::new(1, 1));
+
+        move_solid(&mut world, platform, Vec2::new(0.0, 3.0));
+
+        assert_eq!(position(&world, platform), IVec2::new(0, 3));
+        assert_eq!(position(&world, actor), IVec2::new(1, 4));
+        assert!(world.get::<SquishedMarker>(actor).is_none());
+    }
+
+    #[test]
+    fn falling_platform_carries_riders() {
+        let mut world = world();
+        let platform = spawn_solid(&mut world, IVec2::ZERO, UVec2::new(4, 1));
+        let rider = spawn_actor(&mut world, IVec2::new(1, 1));
+        let bystander = spawn_actor(&mut world, IVec2::new(3, 1));
+        world
+            .get_mut::<RidingEntities>(platform)
+            .unwrap()
+            .insert(rider);
+
+        move_solid(&mut world, platform, Vec2::new(0.0, -2.0));
+
+        assert_eq!(position(&world, platform), IVec2::new(0, -2));
+        assert_eq!(position(&world, rider), IVec2::new(1, -1));
+        assert_eq!(position(&world, bystander), IVec2::new(3, 1));
+    }
+
+    #[test]
+    fn falling_platform_pushes_actor_below() {
+        let mut world = world();
+        let platform = spawn_solid(&mut world, IVec2::new(0, 4), UVec2::new(4, 1));
+        let actor = spawn_actor(&mut world, IVec2::new(1, 1));
+
+        move_solid(&mut world, platform, Vec2::new(0.0, -2.0));
+
+        assert_eq!(position(&world, platform), IVec2::new(0, 2));
+        assert_eq!(position(&world, actor), IVec2::new(1, 0));
+    }
+
+    #[test]
+    fn platform_ignores_actors_in_other_grids() {
+        let mut world = world();
+        let grid = world.spawn_empty().id();
+        let platform = spawn_solid(&mut world, IVec2::ZERO, UVec2::new(4, 1));
+        let actor = spawn_actor(&mut world, IVec2::new(1, 1));
+        world.entity_mut(actor).insert(PhysicsGridMember { grid });
+
+        move_solid(&mut world, platform, Vec2::new(0.0, 3.0));
+
+        assert_eq!(position(&world, platform), IVec2::new(0, 3));
+        assert_eq!(position(&world, actor), IVec2::new(1, 1));
+    }
+
+    #[test]
+    fn rising_platform_squishes_actor_against_ceiling() {
+        let mut world = world();
+        let platform = spawn_solid(&mut world, IVec2::ZERO, UVec2::new(4, 1));
+        spawn_solid(&mut world, IVec2::new(0, 4), UVec2::new(4, 1));
+        let actor = spawn_actor(&mut world, IVec2::new(1, 1));
+
+        move_solid(&mut world, platform, Vec2::new(0.0, 2.0));
+
+        assert!(world.get::<SquishedMarker>(actor).is_some());
+        assert_eq!(world.resource::<Events<ActorSquished>>().len(), 1);
+    }
+
+    #[test]
+    fn actor_jumps_up_through_one_way_platform() {
+        let mut world = world();
+        let platform = spawn_solid(&mut world, IVec2::new(0, 3), UVec2::new(4, 1));
+        world.entity_mut(platform).insert(OneWay(Direction::PosY));
+        let actor = spawn_actor(&mut world, IVec2::new(1, 0));
+
+        move_actor(&mut world, actor, Vec2::new(0.0, 5.0));
+
+        assert_eq!(position(&world, actor), IVec2::new(1, 5));
+    }
+
+    #[test]
+    fn actor_lands_on_one_way_platform() {
+        let mut world = world();
+        let platform = spawn_solid(&mut world, IVec2::new(0, 3), UVec2::new(4, 1));
+        world.entity_mut(platform).insert(OneWay(Direction::PosY));
+        let actor = spawn_actor(&mut world, IVec2::new(1, 6));
+
+        move_actor(&mut world, actor, Vec2::new(0.0, -5.0));
+
+        assert_eq!(position(&world, actor), IVec2::new(1, 4));
+    }
+
+    #[test]
+    fn dropping_actor_falls_through_one_way_platform() {
+        let mut world = world();
+        let platform = spawn_solid(&mut world, IVec2::new(0, 3), UVec2::new(4, 1));
+        world.entity_mut(platform).insert(OneWay(Direction::PosY));
+        let actor = spawn_actor(&mut world, IVec2::new(1, 4));
+        world.entity_mut(actor).insert(DropThrough::default());
+
+        move_actor(&mut world, actor, Vec2::new(0.0, -3.0));
+
+        assert_eq!(position(&world, actor), IVec2::new(1, 1));
+    }
+
+    #[test]
+    fn falling_one_way_platform_passes_actor_below() {
+        let mut world = world();
+        let platform = spawn_solid(&mut world, IVec2::new(0, 4), UVec2::new(4, 1));
+        world.entity_mut(platform).insert(OneWay(Direction::PosY));
+        let actor = spawn_actor(&mut world, IVec2::new(1, 1));
+
+        move_solid(&mut world, platform, Vec2::new(0.0, -4.0));
+
+        assert_eq!(position(&world, platform), IVec2::ZERO);
+        assert_eq!(position(&world, actor), IVec2::new(1, 1));
+    }
+
+    #[test]
+    fn actor_steps_up_ledge() {
+        let mut world = world();
+        spawn_solid(&mut world, IVec2::new(0, -1), UVec2::new(10, 1));
+        spawn_solid(&mut world, IVec2::new(3, 0), UVec2::new(7, 1));
+        let actor = spawn_actor(&mut world, IVec2::new(1, 0));
+        world
+            .entity_mut(actor)
+            .insert(StepHeight { up: 1, down: 1 });
+
+        move_actor(&mut world, actor, Vec2::new(4.0, 0.0));
+
+        assert_eq!(position(&world, actor), IVec2::new(5, 1));
+    }
+
+    #[test]
+    fn actor_stops_at_wall_taller_than_step() {
+        let mut world = world();
+        spawn_solid(&mut world, IVec2::new(3, 0), UVec2::new(1, 2));
+        let actor = spawn_actor(&mut world, IVec2::new(1, 0));
+        world
+            .entity_mut(actor)
+            .insert(StepHeight { up: 1, down: 1 });
+
+        move_actor(&mut world, actor, Vec2::new(4.0, 0.0));
+
+        assert_eq!(position(&world, actor), IVec2::new(2, 0));
+    }
+
+    #[test]
+    fn grounded_actor_walks_down_and_up_stairs() {
+        let mut world = world();
+        for x in 0..4 {
+            spawn_solid(&mut world, IVec2::new(x, -x), UVec2::new(1, 1));
+        }
+        let actor = spawn_actor(&mut world, IVec2::new(0, 1));
+        world
+            .entity_mut(actor)
+            .insert(StepHeight { up: 1, down: 1 });
+
+        move_actor(&mut world, actor, Vec2::new(3.0, 0.0));
+        assert_eq!(position(&world, actor), IVec2::new(3, -2));
+
+        move_actor(&mut world, actor, Vec2::new(-3.0, 0.0));
+        assert_eq!(position(&world, actor), IVec2::new(0, 1));
+    }
+
+    #[test]
+    fn barrier_only_blocks_actors_in_its_filter() {
+        let mut world = world();
+        let barrier = spawn_solid(&mut world, IVec2::new(3, 0), UVec2::new(1, 2));
+        world.get_mut::<Collider>(barrier).unwrap().layers = CollisionLayers::new(1, 2);
+        let player = spawn_actor(&mut world, IVec2::new(1, 0));
+        world.get_mut::<Collider>(player).unwrap().layers = CollisionLayers::new(2, u32::MAX);
+        let enemy = spawn_actor(&mut world, IVec2::new(1, 0));
+
+        move_actor(&mut world, player, Vec2::new(4.0, 0.0));
+        move_actor(&mut world, enemy, Vec2::new(4.0, 0.0));
+
+        assert_eq!(position(&world, player), IVec2::new(2, 0));
+        assert_eq!(position(&world, enemy), IVec2::new(5, 0));
+    }
+
+    #[test]
+    fn rising_platform_passes_through_ghost_actor() {
+        let mut world = world();
+        let platform = spawn_solid(&mut world, IVec2::ZERO, UVec2::new(4, 1));
+        let ghost = spawn_actor(&mut world, IVec2::new(1, 1));
+        world.get_mut::<Collider>(ghost).unwrap().layers = CollisionLayers::NONE;
+
+        move_solid(&mut world, platform, Vec2::new(0.0, 3.0));
+
+        assert_eq!(position(&world, ghost), IVec2::new(1, 1));
     }
 }
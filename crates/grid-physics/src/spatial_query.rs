@@ -0,0 +1,359 @@
+use bevy_ecs::{
+    entity::Entity,
+    system::{Query, Res, SystemParam},
+};
+use bevy_math::{IVec2, UVec2, Vec2};
+use spatial_grid::{grid::PhysicsGridMember, position::Position};
+
+use super::{
+    actor::FilterActors,
+    collision::{Aabb, Collider, CollisionLayers, Overlaps},
+    grid::grid_of,
+    solid::SolidCollisionCaches,
+};
+
+/// Casts are cut short after this many cells, so the area they search stays bounded.
+pub const MAX_CAST_DISTANCE: f32 = 4096.0;
+
+/// What a `SpatialQuery` can hit. Only colliders in `grid` whose layers interact with
+/// `layers` are considered.
+#[derive(Debug, Clone)]
+pub struct SpatialQueryFilter {
+    pub grid: Option<Entity>,
+    pub layers: CollisionLayers,
+    pub solids: bool,
+    pub actors: bool,
+    pub excluded: Vec<Entity>,
+}
+
+impl Default for SpatialQueryFilter {
+    fn default() -> Self {
+        Self {
+            grid: None,
+            layers: CollisionLayers::default(),
+            solids: true,
+            actors: true,
+            excluded: Vec::new(),
+        }
+    }
+}
+
+impl SpatialQueryFilter {
+    pub fn in_grid(mut self, member: Option<&PhysicsGridMember>) -> Self {
+        self.grid = grid_of(member);
+        self
+    }
+
+    pub fn excluding(mut self, entity: Entity) -> Self {
+        self.excluded.push(entity);
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialHit {
+    pub entity: Entity,
+    /// Distance travelled along the cast until the hit, zero if it started overlapping.
+    pub distance: f32,
+    /// Axis aligned normal of the face that was hit, zero if the cast started overlapping.
+    pub normal: IVec2,
+}
+
+/// Raycasts, AABB casts and overlap queries against solids and actors.
+#[derive(SystemParam)]
+pub struct SpatialQuery<'w, 's> {
+    solid_collision_caches: Res<'w, SolidCollisionCaches>,
+    q_actors: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Position,
+            &'static Collider,
+            Option<&'static PhysicsGridMember>,
+        ),
+        FilterActors,
+    >,
+}
+
+impl SpatialQuery<'_, '_> {
+    /// Hits of a ray from `origin` up to `max_distance` cells along `direction`, nearest first.
+    /// Cell `(x, y)` covers `x..x + 1` and `y..y + 1`. `max_distance` is clamped to
+    /// `0.0..=MAX_CAST_DISTANCE` and a NaN distance hits nothing.
+    pub fn cast_ray(
+        &self,
+        origin: Vec2,
+        direction: Vec2,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<SpatialHit> {
+        self.cast(origin, UVec2::ZERO, direction, max_distance, filter)
+    }
+
+    /// Hits of `aabb` moved up to `max_distance` cells along `direction`, nearest first.
+    /// `max_distance` is limited like in `cast_ray`.
+    pub fn cast_aabb(
+        &self,
+        aabb: &Aabb,
+        direction: Vec2,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<SpatialHit> {
+        self.cast(
+            aabb.start.as_vec2(),
+            aabb.size,
+            direction,
+            max_distance,
+            filter,
+        )
+    }
+
+    /// Entities with a collider covering the cell at `point`.
+    pub fn point_overlaps(&self, point: IVec2, filter: &SpatialQueryFilter) -> Vec<Entity> {
+        self.area_overlaps(
+            &Aabb {
+                start: point,
+                size: UVec2::ONE,
+            },
+            filter,
+        )
+    }
+
+    /// Entities with a collider overlapping `aabb`.
+    pub fn area_overlaps(&self, aabb: &Aabb, filter: &SpatialQueryFilter) -> Vec<Entity> {
+        let mut entities = Vec::new();
+        self.for_each_candidate(aabb, filter, |entity, other| {
+            if aabb.overlaps(other) && !entities.contains(&entity) {
+                entities.push(entity);
+            }
+        });
+        entities
+    }
+
+    // Sweeps a `size` box starting at `origin`, a zero size sweeps a point.
+    fn cast(
+        &self,
+        origin: Vec2,
+        size: UVec2,
+        direction: Vec2,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<SpatialHit> {
+        let direction = direction.normalize_or_zero();
+        if direction == Vec2::ZERO || max_distance.is_nan() {
+            return Vec::new();
+        }
+        let max_distance = max_distance.clamp(0.0, MAX_CAST_DISTANCE);
+
+        let end = origin + direction * max_distance;
+        let min = origin.min(end).floor().as_ivec2();
+        let max = origin.max(end).ceil().as_ivec2() + size.as_ivec2();
+        let bounds = Aabb {
+            start: min,
+            size: (max - min).max(IVec2::ONE).as_uvec2(),
+        };
+
+        let mut hits: Vec<SpatialHit> = Vec::new();
+        self.for_each_candidate(&bounds, filter, |entity, other| {
+            let Some((distance, normal)) = sweep(origin, size, direction, other) else {
+                return;
+            };
+            if distance > max_distance {
+                return;
+            }
+            match hits.iter_mut().find(|hit| hit.entity == entity) {
+                Some(hit) if hit.distance <= distance => {}
+                Some(hit) => {
+                    hit.distance = distance;
+                    hit.normal = normal;
+                }
+                None => hits.push(SpatialHit {
+                    entity,
+                    distance,
+                    normal,
+                }),
+            }
+        });
+
+        // Ties are broken by entity so results don't depend on iteration order.
+        hits.sort_by(|a, b| {
+            a.distance
+                .total_cmp(&b.distance)
+                .then(a.entity.cmp(&b.entity))
+        });
+        hits
+    }
+
+    fn for_each_candidate(
+        &self,
+        bounds: &Aabb,
+        filter: &SpatialQueryFilter,
+        mut f: impl FnMut(Entity, &Aabb),
+    ) {
+        if filter.solids {
+            let cache = self.solid_collision_caches.get(filter.grid);
+            for (solid, aabb) in cache.nearby(bounds) {
+                if !filter.excluded.contains(&solid)
+                    && filter.layers.interacts(&cache.layers(solid))
+                {
+                    f(solid, aabb);
+                }
+            }
+        }
+
+        if filter.actors {
+            for (actor, position, collider, member) in self.q_actors.iter() {
+                if grid_of(member) != filter.grid
+                    || filter.excluded.contains(&actor)
+                    || !filter.layers.interacts(&collider.layers)
+                {
+                    continue;
+                }
+                for aabb in collider.shape.iter_at(**position) {
+                    if bounds.overlaps(&aabb) {
+                        f(actor, &aabb);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Entry distance and normal of a `size` box moving from `origin` along the normalized
+/// `direction` into `target`.
+fn sweep(origin: Vec2, size: UVec2, direction: Vec2, target: &Aabb) -> Option<(f32, IVec2)> {
+    // Sweeping a box is the same as sweeping its corner against the target grown by its size.
+    let min = target.start.as_vec2() - size.as_vec2();
+    let max = (target.start + target.size.as_ivec2()).as_vec2();
+
+    let mut enter = 0.0f32;
+    let mut exit = f32::INFINITY;
+    let mut normal = IVec2::ZERO;
+    for (axis, unit) in [(0, IVec2::X), (1, IVec2::Y)] {
+        let (o, d, lo, hi) = (origin[axis], direction[axis], min[axis], max[axis]);
+        if d == 0.0 {
+            // Boxes only touching the target's side don't hit it, points on its start edge do.
+            let inside = o < hi && (o > lo || (size[axis] == 0 && o == lo));
+            if !inside {
+                return None;
+            }
+            continue;
+        }
+        let (near, far) = if d > 0.0 { (lo, hi) } else { (hi, lo) };
+        let t_near = (near - o) / d;
+        let t_far = (far - o) / d;
+        if t_near >= enter {
+            enter = t_near;
+            normal = -unit * d.signum() as i32;
+        }
+        exit = exit.min(t_far);
+    }
+
+    (enter < exit).then_some((enter, normal))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::system::{RunSystemOnce, SystemState};
+
+    use super::*;
+    use crate::{
+        solid::update_collision_cache,
+        test_util::{spawn_actor, spawn_solid, world},
+    };
+
+    #[test]
+    fn ray_hits_are_ordered_with_normals() {
+        let mut world = world();
+        let wall = spawn_solid(&mut world, IVec2::new(10, 0), UVec2::new(2, 2));
+        let actor = spawn_actor(&mut world, IVec2::new(4, 0));
+        world.run_system_once(update_collision_cache).unwrap();
+
+        let mut state = SystemState::<SpatialQuery>::new(&mut world);
+        let query = state.get(&world);
+        let hits = query.cast_ray(
+            Vec2::new(0.0, 1.0),
+            Vec2::X,
+            20.0,
+            &SpatialQueryFilter::default(),
+        );
+        assert_eq!(
+            hits,
+            [
+                SpatialHit {
+                    entity: actor,
+                    distance: 4.0,
+                    normal: IVec2::NEG_X,
+                },
+                SpatialHit {
+                    entity: wall,
+                    distance: 10.0,
+                    normal: IVec2::NEG_X,
+                },
+            ]
+        );
+
+        let solids_only = SpatialQueryFilter {
+            actors: false,
+            ..Default::default()
+        };
+        let hits = query.cast_ray(Vec2::new(0.0, 1.0), Vec2::X, 8.0, &solids_only);
+        assert!(hits.is_empty());
+    }
+
+    #[test]
+    fn aabb_cast_stops_at_floor_and_skips_other_layers() {
+        let mut world = world();
+        let floor = spawn_solid(&mut world, IVec2::new(0, -2), UVec2::new(2, 2));
+        let ghost = spawn_solid(&mut world, IVec2::new(0, 2), UVec2::new(2, 2));
+        world.get_mut::<Collider>(ghost).unwrap().layers = CollisionLayers::new(2, 2);
+        world.run_system_once(update_collision_cache).unwrap();
+
+        let mut state = SystemState::<SpatialQuery>::new(&mut world);
+        let query = state.get(&world);
+        let aabb = Aabb {
+            start: IVec2::new(1, 6),
+            size: UVec2::new(1, 2),
+        };
+        let hits = query.cast_aabb(&aabb, Vec2::NEG_Y, 10.0, &SpatialQueryFilter::default());
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entity, floor);
+        assert_eq!(hits[0].distance, 6.0);
+        assert_eq!(hits[0].normal, IVec2::Y);
+
+        assert_eq!(
+            query.point_overlaps(IVec2::new(1, 3), &SpatialQueryFilter::default()),
+            []
+        );
+        assert_eq!(
+            query.point_overlaps(
+                IVec2::new(1, 3),
+                &SpatialQueryFilter {
+                    layers: CollisionLayers::new(2, 2),
+                    ..Default::default()
+                }
+            ),
+            [ghost]
+        );
+    }
+
+    #[test]
+    fn unbounded_casts_are_clamped() {
+        let mut world = world();
+        let wall = spawn_solid(&mut world, IVec2::new(10, 0), UVec2::new(2, 2));
+        world.run_system_once(update_collision_cache).unwrap();
+
+        let mut state = SystemState::<SpatialQuery>::new(&mut world);
+        let query = state.get(&world);
+        let filter = SpatialQueryFilter::default();
+        let hits = query.cast_ray(Vec2::new(0.0, 1.0), Vec2::X, f32::INFINITY, &filter);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entity, wall);
+        assert!(query
+            .cast_ray(Vec2::new(0.0, 1.0), Vec2::X, f32::NAN, &filter)
+            .is_empty());
+        assert!(query
+            .cast_ray(Vec2::new(0.0, 1.0), Vec2::NEG_X, f32::NEG_INFINITY, &filter)
+            .is_empty());
+    }
+}
@@ -0,0 +1,216 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::Event,
+    query::With,
+    system::{Commands, Query, Res},
+};
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_math::IVec2;
+use spatial_grid::{grid::PhysicsGridMember, position::Position, remainder::Remainder};
+
+use super::{
+    actor::FilterActors,
+    collision::Collider,
+    grid::grid_of,
+    solid::{SolidCollisionCache, SolidCollisionCaches},
+    velocity::Velocity,
+};
+
+/// Sent when a moving solid can't push an actor out of the way.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ActorSquished {
+    pub actor: Entity,
+    pub solid: Entity,
+}
+
+/// Inserted on squished actors until their `SquishResponse` is applied.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct SquishedMarker {
+    pub solid: Entity,
+}
+
+/// What happens to an actor after being squished, actors without one are nudged.
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub enum SquishResponse {
+    /// Only send `ActorSquished`.
+    Ignore,
+    Kill,
+    Respawn(IVec2),
+    /// Move to the nearest free cell within `NUDGE_RADIUS`.
+    #[default]
+    Nudge,
+}
+
+const NUDGE_RADIUS: i32 = 8;
+
+type SquishedQueryData<'a> = (
+    Entity,
+    &'a mut Position,
+    &'a mut Remainder,
+    &'a Collider,
+    Option<&'a SquishResponse>,
+    Option<&'a mut Velocity>,
+    Option<&'a PhysicsGridMember>,
+);
+
+pub(crate) fn resolve_squished_actors(
+    mut commands: Commands,
+    mut q_squished: Query<SquishedQueryData, (FilterActors, With<SquishedMarker>)>,
+    solid_collision_caches: Res<SolidCollisionCaches>,
+) {
+    for (actor, mut position, mut remainder, collider, response, velocity, member) in
+        q_squished.iter_mut()
+    {
+        commands.entity(actor).remove::<SquishedMarker>();
+
+        match response.copied().unwrap_or_default() {
+            SquishResponse::Ignore => {}
+            SquishResponse::Kill => {
+                commands.entity(actor).despawn_recursive();
+            }
+            SquishResponse::Respawn(respawn) => {
+                **position = respawn;
+                *remainder = Remainder::default();
+                if let Some(mut velocity) = velocity {
+                    **velocity = Default::default();
+                }
+            }
+            SquishResponse::Nudge => {
+                if let Some(free) = nearest_free_cell(
+                    **position,
+                    collider,
+                    solid_collision_caches.get(grid_of(member)),
+                ) {
+                    **position = free;
+                    *remainder = Remainder::default();
+                }
+            }
+        }
+    }
+}
+
+// Searches rings of increasing distance, preferring cells above the actor on ties.
+fn nearest_free_cell(
+    position: IVec2,
+    collider: &Collider,
+    collision_cache: &SolidCollisionCache,
+) -> Option<IVec2> {
+    (0..=NUDGE_RADIUS).find_map(|radius| {
+        let mut ring = (-radius..=radius)
+            .flat_map(|x| (-radius..=radius).map(move |y| IVec2::new(x, y)))
+            .filter(|offset| offset.x.abs().max(offset.y.abs()) == radius)
+            .collect::<Vec<_>>();
+        ring.sort_by_key(|offset| (offset.x.abs() + offset.y.abs(), -offset.y));
+        ring.into_iter()
+            .map(|offset| position + offset)
+            .find(|&cell| collider.overlaps(cell, collision_cache).is_none())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_hierarchy::BuildChildren;
+    use bevy_math::{UVec2, Vec2};
+
+    use super::*;
+    use crate::{
+        solid::update_collision_cache,
+        test_util::{self, collider, position, spawn_actor, spawn_solid},
+    };
+
+    fn world() -> World {
+        let mut world = test_util::world();
+        // Squished actors start inside the solid at the origin, next to the others.
+        for position in [IVec2::new(0, 0), IVec2::new(0, 2), IVec2::new(-1, 0)] {
+            spawn_solid(&mut world, position, UVec2::ONE);
+        }
+        world.run_system_once(update_collision_cache).unwrap();
+        world
+    }
+
+    fn spawn_squished(world: &mut World, response: Option<SquishResponse>) -> Entity {
+        let actor = spawn_actor(world, IVec2::ZERO);
+        let mut actor = world.entity_mut(actor);
+        actor.insert((
+            Remainder(Vec2::new(0.5, 0.5)),
+            collider(UVec2::ONE),
+            Velocity(Vec2::new(3.0, 4.0)),
+            SquishedMarker {
+                solid: Entity::PLACEHOLDER,
+            },
+        ));
+        if let Some(response) = response {
+            actor.insert(response);
+        }
+        actor.id()
+    }
+
+    fn resolve(world: &mut World) {
+        world.run_system_once(resolve_squished_actors).unwrap();
+    }
+
+    #[test]
+    fn ignore_only_removes_the_marker() {
+        let mut world = world();
+        let actor = spawn_squished(&mut world, Some(SquishResponse::Ignore));
+        resolve(&mut world);
+
+        assert!(world.get::<SquishedMarker>(actor).is_none());
+        assert_eq!(position(&world, actor), IVec2::ZERO);
+    }
+
+    #[test]
+    fn kill_despawns_the_actor_and_its_children() {
+        let mut world = world();
+        let actor = spawn_squished(&mut world, Some(SquishResponse::Kill));
+        let child = world.spawn_empty().set_parent(actor).id();
+        resolve(&mut world);
+
+        assert!(world.get_entity(actor).is_err());
+        assert!(world.get_entity(child).is_err());
+    }
+
+    #[test]
+    fn respawn_resets_position_and_velocity() {
+        let mut world = world();
+        let actor = spawn_squished(&mut world, Some(SquishResponse::Respawn(IVec2::new(5, 6))));
+        resolve(&mut world);
+
+        assert_eq!(position(&world, actor), IVec2::new(5, 6));
+        assert_eq!(**world.get::<Velocity>(actor).unwrap(), Vec2::ZERO);
+        assert_eq!(**world.get::<Remainder>(actor).unwrap(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn nudge_is_the_default_response() {
+        let mut world = world();
+        let actor = spawn_squished(&mut world, None);
+        resolve(&mut world);
+
+        assert_eq!(position(&world, actor), IVec2::new(0, 1));
+        assert_eq!(**world.get::<Remainder>(actor).unwrap(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn nearest_free_cell_prefers_cells_above() {
+        let world = world();
+        let cache = world.resource::<SolidCollisionCaches>().get(None);
+        let cell = collider(UVec2::ONE);
+
+        assert_eq!(
+            nearest_free_cell(IVec2::new(0, 1), &cell, cache),
+            Some(IVec2::new(0, 1))
+        );
+        // (0, 1) and (1, 0) are equally close, the cell above wins.
+        assert_eq!(
+            nearest_free_cell(IVec2::ZERO, &cell, cache),
+            Some(IVec2::new(0, 1))
+        );
+        assert_eq!(
+            nearest_free_cell(IVec2::ZERO, &collider(UVec2::new(1, 3)), cache),
+            Some(IVec2::new(1, 0))
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use bevy_ecs::{entity::Entity, event::Events, world::World};
+use bevy_math::{IVec2, UVec2};
+use spatial_grid::{position::Position, remainder::Remainder};
+
+use crate::{
+    actor::Actor,
+    collision::{Aabb, Collider},
+    contact::{ActorContactCache, ActorContactEnded, ActorContactStarted},
+    movement::Movement,
+    sensor::{TriggerEntered, TriggerExited},
+    solid::{RidingEntities, Solid, SolidCollisionCaches},
+    squish::ActorSquished,
+};
+
+/// A collider of `size` cells starting at the entity's position.
+pub(crate) fn collider(size: UVec2) -> Collider {
+    Collider {
+        shape: Aabb {
+            start: IVec2::ZERO,
+            size,
+        }
+        .into(),
+        ..Default::default()
+    }
+}
+
+/// A world with the collision caches and the events physics systems send.
+pub(crate) fn world() -> World {
+    let mut world = World::new();
+    world.init_resource::<SolidCollisionCaches>();
+    world.init_resource::<ActorContactCache>();
+    world.init_resource::<Events<ActorSquished>>();
+    world.init_resource::<Events<ActorContactStarted>>();
+    world.init_resource::<Events<ActorContactEnded>>();
+    world.init_resource::<Events<TriggerEntered>>();
+    world.init_resource::<Events<TriggerExited>>();
+    world
+}
+
+/// A one cell wide, two cell tall actor.
+pub(crate) fn spawn_actor(world: &mut World, position: IVec2) -> Entity {
+    world
+        .spawn((
+            Actor,
+            Position(position),
+            Remainder::default(),
+            collider(UVec2::new(1, 2)),
+            Movement::default(),
+        ))
+        .id()
+}
+
+pub(crate) fn spawn_solid(world: &mut World, position: IVec2, size: UVec2) -> Entity {
+    world
+        .spawn((
+            Solid,
+            Position(position),
+            Remainder::default(),
+            collider(size),
+            Movement::default(),
+            RidingEntities::default(),
+        ))
+        .id()
+}
+
+pub(crate) fn position(world: &World, entity: Entity) -> IVec2 {
+    **world.get::<Position>(entity).unwrap()
+}
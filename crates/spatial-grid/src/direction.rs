@@ -1,4 +1,5 @@
 use bevy_ecs::component::Component;
+use bevy_math::IVec2;
 
 #[derive(Debug, Component, Clone, Copy)]
 pub enum Direction {
@@ -7,3 +8,14 @@ pub enum Direction {
     NegX,
     NegY,
 }
+
+impl Direction {
+    pub fn as_ivec2(self) -> IVec2 {
+        match self {
+            Direction::PosX => IVec2::X,
+            Direction::PosY => IVec2::Y,
+            Direction::NegX => IVec2::NEG_X,
+            Direction::NegY => IVec2::NEG_Y,
+        }
+    }
+}
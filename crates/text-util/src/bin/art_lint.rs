@@ -0,0 +1,89 @@
+use std::{env, fs, process::ExitCode};
+use text_util::text_art::{lint_art, normalise_art, ArtLintOptions, DEFAULT_CHARSET};
+
+const USAGE: &str = "Usage: art_lint [--fix] [--frame WIDTHxHEIGHT] [--charset default|FILE] [--tab-width N] FILES...";
+
+fn main() -> ExitCode {
+    let mut options = ArtLintOptions::default();
+    let mut fix = false;
+    let mut files = Vec::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--fix" => fix = true,
+            "--frame" => {
+                let Some(size) = args.next().and_then(|size| {
+                    let (width, height) = size.split_once('x')?;
+                    Some((width.parse().ok()?, height.parse().ok()?))
+                }) else {
+                    println!("{}", USAGE);
+                    return ExitCode::FAILURE;
+                };
+                options.frame_size = Some(size);
+            }
+            "--charset" => {
+                let charset = match args.next().as_deref() {
+                    Some("default") => DEFAULT_CHARSET.to_string(),
+                    Some(path) => {
+                        let Ok(charset) = fs::read_to_string(path) else {
+                            println!("Failed to open {}", path);
+                            return ExitCode::FAILURE;
+                        };
+                        charset
+                    }
+                    None => {
+                        println!("{}", USAGE);
+                        return ExitCode::FAILURE;
+                    }
+                };
+                options.charset = Some(charset.chars().filter(|c| !c.is_whitespace()).collect());
+            }
+            "--tab-width" => {
+                let Some(tab_width) = args.next().and_then(|n| n.parse().ok()) else {
+                    println!("{}", USAGE);
+                    return ExitCode::FAILURE;
+                };
+                options.tab_width = tab_width;
+            }
+            _ => files.push(arg),
+        }
+    }
+
+    if files.is_empty() {
+        println!("{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = false;
+    for file in files.iter() {
+        let Ok(bytes) = fs::read(file) else {
+            println!("Failed to open {}", file);
+            failed = true;
+            continue;
+        };
+
+        let issues = lint_art(&bytes, &options);
+        for issue in issues.iter() {
+            match issue.line() {
+                Some(_) => println!("{}:{}", file, issue),
+                None => println!("{}: {}", file, issue),
+            }
+        }
+
+        if fix && !issues.is_empty() {
+            if fs::write(file, normalise_art(&bytes, &options)).is_err() {
+                println!("Failed to write {}", file);
+                failed = true;
+            }
+        } else if !issues.is_empty() {
+            failed = true;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,77 @@
+use std::{env, fs, io::BufReader, process::ExitCode};
+use text_util::text_image::{image_to_art, Image, ImageArtOptions};
+
+const USAGE: &str = "Usage: img2art INPUT.png OUTPUT.art [--cell WIDTHxHEIGHT] [--ramp CHARS] [--edges THRESHOLD] [--invert] [--markup FILE]";
+
+fn main() -> ExitCode {
+    let mut options = ImageArtOptions::default();
+    let mut markup = None;
+    let mut files = Vec::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let valid = match arg.as_str() {
+            "--cell" => args
+                .next()
+                .and_then(|size| {
+                    let (width, height) = size.split_once('x')?;
+                    Some((width.parse().ok()?, height.parse().ok()?))
+                })
+                .map(|size| options.cell_size = size)
+                .is_some(),
+            "--ramp" => args
+                .next()
+                .map(|ramp| options.ramp = ramp.chars().collect())
+                .is_some(),
+            "--edges" => args
+                .next()
+                .and_then(|threshold| threshold.parse().ok())
+                .map(|threshold| options.edge_threshold = Some(threshold))
+                .is_some(),
+            "--invert" => {
+                options.invert = true;
+                true
+            }
+            "--markup" => args.next().map(|file| markup = Some(file)).is_some(),
+            _ => {
+                files.push(arg);
+                true
+            }
+        };
+        if !valid {
+            println!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let [input, output] = files.as_slice() else {
+        println!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+
+    let Ok(file) = fs::File::open(input) else {
+        println!("Failed to open {}", input);
+        return ExitCode::FAILURE;
+    };
+    let image = match Image::from_png(BufReader::new(file)) {
+        Ok(image) => image,
+        Err(error) => {
+            println!("Failed to decode {}: {}", input, error);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let art = image_to_art(&image, &options);
+    if fs::write(output, art.lines.join("\n")).is_err() {
+        println!("Failed to write {}", output);
+        return ExitCode::FAILURE;
+    }
+    if let Some(markup) = markup {
+        if fs::write(&markup, art.markup()).is_err() {
+            println!("Failed to write {}", markup);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,66 @@
+use std::{
+    io::{self, Read, Write},
+    path::Path,
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder};
+use ruzstd::{
+    decoding::StreamingDecoder,
+    encoding::{compress_to_vec, CompressionLevel},
+};
+
+/// Compression applied to an asset file, selected by a trailing `.gz` or `.zst` extension.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("gz") => Self::Gzip,
+            Some("zst") => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+
+    /// Appends the `.gz` or `.zst` extension to an uncompressed file name.
+    pub fn file_name(self, name: &str) -> String {
+        match self {
+            Self::None => name.into(),
+            Self::Gzip => format!("{}.gz", name),
+            Self::Zstd => format!("{}.zst", name),
+        }
+    }
+
+    pub fn decompress(self, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        match self {
+            Self::None => return Ok(bytes),
+            Self::Gzip => {
+                GzDecoder::new(bytes.as_slice()).read_to_end(&mut data)?;
+            }
+            Self::Zstd => {
+                StreamingDecoder::new(bytes.as_slice())
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?
+                    .read_to_end(&mut data)?;
+            }
+        }
+        Ok(data)
+    }
+
+    pub fn compress(self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Self::None => Ok(bytes.to_vec()),
+            Self::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+            Self::Zstd => Ok(compress_to_vec(bytes, CompressionLevel::Fastest)),
+        }
+    }
+}
@@ -1 +1,8 @@
+pub mod compression;
+pub mod text_art;
+pub mod text_flip;
+pub mod text_image;
+pub mod text_markup;
 pub mod text_mirror;
+pub mod text_rotate;
+pub mod text_width;
@@ -0,0 +1,362 @@
+use std::{fmt, string::FromUtf8Error};
+
+use hashbrown::HashSet;
+
+use crate::text_width::{cells_to_line, char_width, line_cells, line_width};
+
+pub const DEFAULT_CHARSET: &str = "!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_´`abcdefghijklmnopqrstuvwxyz{|}~";
+
+/// Splits art into lines the same way the asset loaders do, `\r` before a newline is dropped.
+pub fn parse_art_lines(bytes: &[u8]) -> Result<Vec<String>, FromUtf8Error> {
+    bytes
+        .split(|&b| b == b'\n')
+        .map(|line| String::from_utf8(line.strip_suffix(b"\r").unwrap_or(line).to_vec()))
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct ArtLintOptions {
+    /// Width and height of a single frame or tile, the art must be a whole number of frames.
+    pub frame_size: Option<(usize, usize)>,
+    /// Characters the art may use besides spaces.
+    pub charset: Option<HashSet<char>>,
+    pub tab_width: usize,
+}
+
+impl Default for ArtLintOptions {
+    fn default() -> Self {
+        Self {
+            frame_size: None,
+            charset: None,
+            tab_width: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtLintIssue {
+    InvalidUtf8 {
+        line: usize,
+    },
+    CrLf {
+        line: usize,
+    },
+    Tab {
+        line: usize,
+        column: usize,
+    },
+    TrailingNewline,
+    TrailingWhitespace {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+    RaggedWidth {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+    RaggedHeight {
+        height: usize,
+        expected: usize,
+    },
+    MissingCharacter {
+        line: usize,
+        column: usize,
+        character: char,
+    },
+}
+
+impl ArtLintIssue {
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::InvalidUtf8 { line }
+            | Self::CrLf { line }
+            | Self::Tab { line, .. }
+            | Self::TrailingWhitespace { line, .. }
+            | Self::RaggedWidth { line, .. }
+            | Self::MissingCharacter { line, .. } => Some(*line),
+            Self::TrailingNewline | Self::RaggedHeight { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for ArtLintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUtf8 { line } => write!(f, "{}: invalid UTF-8", line),
+            Self::CrLf { line } => write!(f, "{}: CRLF line ending", line),
+            Self::Tab { line, column } => write!(f, "{}:{}: tab character", line, column),
+            Self::TrailingNewline => write!(f, "trailing newline adds an empty row"),
+            Self::TrailingWhitespace {
+                line,
+                width,
+                expected,
+            } => write!(
+                f,
+                "{}: trailing whitespace extends the line to {} cells, expected {}",
+                line, width, expected
+            ),
+            Self::RaggedWidth {
+                line,
+                width,
+                expected,
+            } => write!(
+                f,
+                "{}: line is {} cells wide, expected {}",
+                line, width, expected
+            ),
+            Self::RaggedHeight { height, expected } => {
+                write!(f, "art is {} lines high, expected {}", height, expected)
+            }
+            Self::MissingCharacter {
+                line,
+                column,
+                character,
+            } => write!(
+                f,
+                "{}:{}: {:?} is not in the character set",
+                line, column, character
+            ),
+        }
+    }
+}
+
+/// Lists problems that make art load incorrectly, lines and columns start at 1.
+pub fn lint_art(bytes: &[u8], options: &ArtLintOptions) -> Vec<ArtLintIssue> {
+    let mut issues = Vec::new();
+
+    let raw_lines = bytes.split(|&b| b == b'\n').collect::<Vec<_>>();
+    if raw_lines.len() > 1 && raw_lines.last().is_some_and(|line| line.is_empty()) {
+        issues.push(ArtLintIssue::TrailingNewline);
+    }
+
+    for (index, raw) in raw_lines.iter().enumerate() {
+        let line = index + 1;
+        if raw.ends_with(b"\r") {
+            issues.push(ArtLintIssue::CrLf { line });
+        }
+        let Ok(text) = std::str::from_utf8(raw) else {
+            issues.push(ArtLintIssue::InvalidUtf8 { line });
+            continue;
+        };
+
+        let mut column = 1;
+        for c in text.trim_end_matches('\r').chars() {
+            if c == '\t' {
+                issues.push(ArtLintIssue::Tab { line, column });
+            } else if c != ' '
+                && options
+                    .charset
+                    .as_ref()
+                    .is_some_and(|charset| !charset.contains(&c))
+            {
+                issues.push(ArtLintIssue::MissingCharacter {
+                    line,
+                    column,
+                    character: c,
+                });
+            }
+            column += char_width(c).max(1);
+        }
+    }
+
+    let lines = normalised_lines(bytes, options.tab_width);
+    let (width, height) = expected_size(&lines, options.frame_size);
+    for (index, text) in lines.iter().enumerate() {
+        let line_width = line_width(text);
+        if line_width > width {
+            issues.push(ArtLintIssue::TrailingWhitespace {
+                line: index + 1,
+                width: line_width,
+                expected: width,
+            });
+        } else if line_width < width {
+            issues.push(ArtLintIssue::RaggedWidth {
+                line: index + 1,
+                width: line_width,
+                expected: width,
+            });
+        }
+    }
+    if lines.len() != height {
+        issues.push(ArtLintIssue::RaggedHeight {
+            height: lines.len(),
+            expected: height,
+        });
+    }
+
+    issues
+}
+
+/// Rewrites art with LF line endings, tabs expanded, no trailing newline and every line
+/// padded or trimmed to the same width. Characters missing from the charset are kept.
+pub fn normalise_art(bytes: &[u8], options: &ArtLintOptions) -> String {
+    let mut lines = normalised_lines(bytes, options.tab_width);
+    let (width, height) = expected_size(&lines, options.frame_size);
+
+    lines.resize(height, String::new());
+    for line in lines.iter_mut() {
+        let mut cells = line_cells(line);
+        cells.resize(width, ' ');
+        *line = cells_to_line(&cells);
+    }
+
+    lines.join("\n")
+}
+
+fn normalised_lines(bytes: &[u8], tab_width: usize) -> Vec<String> {
+    let text = String::from_utf8_lossy(bytes).replace("\r\n", "\n");
+    let mut lines = text
+        .split('\n')
+        .map(|line| expand_tabs(line, tab_width))
+        .collect::<Vec<_>>();
+    if lines.len() > 1 && lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines
+}
+
+fn expand_tabs(line: &str, tab_width: usize) -> String {
+    let tab_width = tab_width.max(1);
+    let mut expanded = String::with_capacity(line.len());
+    let mut width = 0;
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = tab_width - width % tab_width;
+            expanded.extend(std::iter::repeat_n(' ', spaces));
+            width += spaces;
+        } else {
+            expanded.push(c);
+            width += char_width(c);
+        }
+    }
+    expanded
+}
+
+// Trailing whitespace doesn't widen the art, only the frame size rounds it up.
+fn expected_size(lines: &[String], frame_size: Option<(usize, usize)>) -> (usize, usize) {
+    let width = lines
+        .iter()
+        .map(|line| line_width(line.trim_end_matches(' ')))
+        .max()
+        .unwrap_or(0);
+    let height = lines.len();
+
+    match frame_size {
+        Some((frame_width, frame_height)) => (
+            width.div_ceil(frame_width.max(1)) * frame_width,
+            height.div_ceil(frame_height.max(1)) * frame_height,
+        ),
+        None => (width, height),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(art: &str) -> Vec<ArtLintIssue> {
+        lint_art(art.as_bytes(), &ArtLintOptions::default())
+    }
+
+    #[test]
+    fn clean_art_has_no_issues() {
+        assert_eq!(lint("/\\\n\\/"), []);
+    }
+
+    #[test]
+    fn line_endings_and_tabs() {
+        assert_eq!(
+            lint("ab\r\na\tb\n"),
+            [
+                ArtLintIssue::TrailingNewline,
+                ArtLintIssue::CrLf { line: 1 },
+                ArtLintIssue::Tab { line: 2, column: 2 },
+                ArtLintIssue::RaggedWidth {
+                    line: 1,
+                    width: 2,
+                    expected: 5,
+                },
+            ]
+        );
+        assert_eq!(
+            lint_art(&[b'a', 0xff], &ArtLintOptions::default()),
+            [ArtLintIssue::InvalidUtf8 { line: 1 }]
+        );
+    }
+
+    #[test]
+    fn widths_and_heights() {
+        assert_eq!(
+            lint("abc\na\nab "),
+            [ArtLintIssue::RaggedWidth {
+                line: 2,
+                width: 1,
+                expected: 3,
+            },]
+        );
+        assert_eq!(
+            lint("abc\nabcd "),
+            [
+                ArtLintIssue::RaggedWidth {
+                    line: 1,
+                    width: 3,
+                    expected: 4,
+                },
+                ArtLintIssue::TrailingWhitespace {
+                    line: 2,
+                    width: 5,
+                    expected: 4,
+                },
+            ]
+        );
+
+        let options = ArtLintOptions {
+            frame_size: Some((2, 2)),
+            ..Default::default()
+        };
+        assert_eq!(
+            lint_art(b"ab\nab\nab", &options),
+            [ArtLintIssue::RaggedHeight {
+                height: 3,
+                expected: 4,
+            },]
+        );
+    }
+
+    #[test]
+    fn characters_outside_the_charset() {
+        let options = ArtLintOptions {
+            charset: Some("ab".chars().collect()),
+            ..Default::default()
+        };
+        assert_eq!(
+            lint_art("a 字c".as_bytes(), &options),
+            [
+                ArtLintIssue::MissingCharacter {
+                    line: 1,
+                    column: 3,
+                    character: '字',
+                },
+                ArtLintIssue::MissingCharacter {
+                    line: 1,
+                    column: 5,
+                    character: 'c',
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn normalise_fixes_lint_issues() {
+        let options = ArtLintOptions {
+            frame_size: Some((4, 2)),
+            tab_width: 2,
+            ..Default::default()
+        };
+        let normalised = normalise_art(b"a\tb\r\nab  \n", &options);
+        assert_eq!(normalised, "a b \nab  ");
+        assert_eq!(lint_art(normalised.as_bytes(), &options), []);
+    }
+}
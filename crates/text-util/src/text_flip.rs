@@ -0,0 +1,47 @@
+use hashbrown::HashMap;
+
+pub fn flip_lines_with(lines: &[String], map: &HashMap<char, char>) -> Vec<String> {
+    lines
+        .iter()
+        .rev()
+        .map(|line| line.chars().map(|c| *map.get(&c).unwrap_or(&c)).collect())
+        .collect()
+}
+
+pub fn flip_lines(lines: &[String]) -> Vec<String> {
+    flip_lines_with(lines, &flip_map())
+}
+
+pub fn flip_map() -> HashMap<char, char> {
+    HashMap::from_iter(
+        FLIP_CHARS
+            .into_iter()
+            .chain(FLIP_CHARS.into_iter().map(|(a, b)| (b, a))),
+    )
+}
+
+const FLIP_CHARS: [(char, char); 12] = [
+    ('/', '\\'),
+    ('^', 'v'),
+    ('\'', '.'),
+    ('`', ','),
+    ('_', '‾'),
+    ('u', 'n'),
+    ('M', 'W'),
+    ('┌', '└'),
+    ('┐', '┘'),
+    ('┬', '┴'),
+    ('╔', '╚'),
+    ('╗', '╝'),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flips_reverse_lines_and_direction_sensitive_characters() {
+        let lines = ["/^ x".to_string(), "_u└".to_string()];
+        assert_eq!(flip_lines(&lines), ["‾n┌", "\\v x"]);
+    }
+}
@@ -0,0 +1,314 @@
+use std::io::Read;
+
+use png::{ColorType, Decoder, DecodingError, Transformations};
+
+pub const DEFAULT_RAMP: &str = " .:-=+*#%@";
+
+#[derive(Debug, Clone)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    pub fn from_png(reader: impl Read) -> Result<Self, DecodingError> {
+        let mut decoder = Decoder::new(reader);
+        decoder.set_transformations(Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer)?;
+        let bytes = &buffer[..info.buffer_size()];
+
+        let pixels = match info.color_type {
+            ColorType::Rgba => bytes
+                .chunks_exact(4)
+                .map(|p| [p[0], p[1], p[2], p[3]])
+                .collect(),
+            ColorType::Rgb => bytes
+                .chunks_exact(3)
+                .map(|p| [p[0], p[1], p[2], 0xff])
+                .collect(),
+            ColorType::GrayscaleAlpha => bytes
+                .chunks_exact(2)
+                .map(|p| [p[0], p[0], p[0], p[1]])
+                .collect(),
+            ColorType::Grayscale | ColorType::Indexed => {
+                bytes.iter().map(|&v| [v, v, v, 0xff]).collect()
+            }
+        };
+
+        Ok(Self {
+            width: info.width as usize,
+            height: info.height as usize,
+            pixels,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageArtOptions {
+    /// Characters from least to most dense.
+    pub ramp: Vec<char>,
+    /// Pixels covered by one cell, cells are usually about twice as tall as wide.
+    pub cell_size: (usize, usize),
+    /// Minimum brightness gradient, between 0 and 1, drawn with `/ \ | -` instead of the ramp.
+    pub edge_threshold: Option<f32>,
+    /// Use dense characters for dark pixels, for art shown on a light background.
+    pub invert: bool,
+}
+
+impl Default for ImageArtOptions {
+    fn default() -> Self {
+        Self {
+            ramp: DEFAULT_RAMP.chars().collect(),
+            cell_size: (4, 8),
+            edge_threshold: None,
+            invert: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageArt {
+    pub lines: Vec<String>,
+    /// Average colour of each cell, `None` for transparent cells.
+    pub colors: Vec<Vec<Option<[u8; 3]>>>,
+}
+
+impl ImageArt {
+    /// The art with a colour tag per run of equally coloured cells, see `text_markup`.
+    /// Spaces continue the current run and transparent cells are left uncoloured.
+    pub fn markup(&self) -> String {
+        self.lines
+            .iter()
+            .zip(self.colors.iter())
+            .map(|(line, colors)| {
+                let mut markup = String::new();
+                let mut current = None;
+                for (c, &color) in line.chars().zip(colors.iter()) {
+                    let color = if c == ' ' { current } else { color };
+                    if color != current {
+                        if current.is_some() {
+                            markup.push_str("[/]");
+                        }
+                        if let Some([r, g, b]) = color {
+                            markup.push_str(&format!("[color=#{:02x}{:02x}{:02x}]", r, g, b));
+                        }
+                        current = color;
+                    }
+                    if c == '[' {
+                        markup.push('[');
+                    }
+                    markup.push(c);
+                }
+                if current.is_some() {
+                    markup.push_str("[/]");
+                }
+                markup
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+struct Cell {
+    brightness: f32,
+    color: Option<[u8; 3]>,
+}
+
+pub fn image_to_art(image: &Image, options: &ImageArtOptions) -> ImageArt {
+    let (cell_width, cell_height) = (options.cell_size.0.max(1), options.cell_size.1.max(1));
+    let columns = image.width.div_ceil(cell_width);
+    let rows = image.height.div_ceil(cell_height);
+
+    let cells = (0..rows)
+        .map(|row| {
+            (0..columns)
+                .map(|column| average_cell(image, column, row, cell_width, cell_height))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let brightness = |x: usize, y: usize| {
+        let x = x.min(columns - 1);
+        let y = y.min(rows - 1);
+        cells[y][x].brightness
+    };
+
+    let mut lines = Vec::with_capacity(rows);
+    for (y, row) in cells.iter().enumerate() {
+        let mut line = String::with_capacity(columns);
+        for (x, cell) in row.iter().enumerate() {
+            if cell.color.is_none() {
+                line.push(' ');
+                continue;
+            }
+
+            // Sobel operator over neighbouring cells, y grows downwards.
+            let (left, up) = (x.saturating_sub(1), y.saturating_sub(1));
+            let gx =
+                (brightness(x + 1, up) + 2.0 * brightness(x + 1, y) + brightness(x + 1, y + 1))
+                    - (brightness(left, up) + 2.0 * brightness(left, y) + brightness(left, y + 1));
+            let gy =
+                (brightness(left, y + 1) + 2.0 * brightness(x, y + 1) + brightness(x + 1, y + 1))
+                    - (brightness(left, up) + 2.0 * brightness(x, up) + brightness(x + 1, up));
+            let magnitude = (gx * gx + gy * gy).sqrt() / 4.0;
+
+            if options
+                .edge_threshold
+                .is_some_and(|threshold| magnitude >= threshold)
+            {
+                line.push(edge_char(gx, gy));
+            } else {
+                line.push(ramp_char(&options.ramp, cell.brightness, options.invert));
+            }
+        }
+        lines.push(line);
+    }
+
+    ImageArt {
+        lines,
+        colors: cells
+            .into_iter()
+            .map(|row| row.into_iter().map(|cell| cell.color).collect())
+            .collect(),
+    }
+}
+
+fn average_cell(image: &Image, column: usize, row: usize, width: usize, height: usize) -> Cell {
+    let mut sum = [0.0f32; 4];
+    let mut count = 0.0;
+    for y in row * height..((row + 1) * height).min(image.height) {
+        for x in column * width..((column + 1) * width).min(image.width) {
+            let [r, g, b, a] = image.pixels[x + y * image.width];
+            let alpha = a as f32 / 255.0;
+            sum[0] += r as f32 * alpha;
+            sum[1] += g as f32 * alpha;
+            sum[2] += b as f32 * alpha;
+            sum[3] += alpha;
+            count += 1.0;
+        }
+    }
+
+    if count == 0.0 || sum[3] / count < 0.5 {
+        return Cell {
+            brightness: 0.0,
+            color: None,
+        };
+    }
+
+    let color = [sum[0] / sum[3], sum[1] / sum[3], sum[2] / sum[3]];
+    Cell {
+        brightness: (0.2126 * color[0] + 0.7152 * color[1] + 0.0722 * color[2]) / 255.0,
+        color: Some(color.map(|c| c.round() as u8)),
+    }
+}
+
+fn ramp_char(ramp: &[char], brightness: f32, invert: bool) -> char {
+    if ramp.is_empty() {
+        return ' ';
+    }
+    let brightness = if invert { 1.0 - brightness } else { brightness };
+    let index = (brightness.clamp(0.0, 1.0) * (ramp.len() - 1) as f32).round() as usize;
+    ramp[index]
+}
+
+// Edges run perpendicular to the brightness gradient.
+fn edge_char(gx: f32, gy: f32) -> char {
+    let angle = gy.atan2(gx).to_degrees().rem_euclid(180.0);
+    match angle {
+        a if !(22.5..157.5).contains(&a) => '|',
+        a if a < 67.5 => '/',
+        a if a < 112.5 => '-',
+        _ => '\\',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text_markup::parse_markup;
+
+    const WHITE: [u8; 4] = [0xff, 0xff, 0xff, 0xff];
+    const BLACK: [u8; 4] = [0x00, 0x00, 0x00, 0xff];
+    const CLEAR: [u8; 4] = [0x00, 0x00, 0x00, 0x00];
+
+    fn image(width: usize, pixels: &[[u8; 4]]) -> Image {
+        Image {
+            width,
+            height: pixels.len() / width,
+            pixels: pixels.to_vec(),
+        }
+    }
+
+    fn options() -> ImageArtOptions {
+        ImageArtOptions {
+            cell_size: (1, 1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn brightness_picks_ramp_characters() {
+        let art = image_to_art(&image(3, &[WHITE, BLACK, CLEAR]), &options());
+        assert_eq!(art.lines, ["@  "]);
+        assert_eq!(art.colors, [[Some([0xff; 3]), Some([0x00; 3]), None]]);
+
+        let inverted = ImageArtOptions {
+            invert: true,
+            ..options()
+        };
+        assert_eq!(
+            image_to_art(&image(3, &[WHITE, BLACK, CLEAR]), &inverted).lines,
+            [" @ "]
+        );
+    }
+
+    #[test]
+    fn cells_average_their_pixels() {
+        let art = image_to_art(
+            &image(2, &[WHITE, BLACK, WHITE, BLACK]),
+            &ImageArtOptions {
+                cell_size: (2, 2),
+                ..Default::default()
+            },
+        );
+        assert_eq!(art.lines, ["+"]);
+        assert_eq!(art.colors, [[Some([0x80; 3])]]);
+    }
+
+    #[test]
+    fn edges_follow_brightness_changes() {
+        let art = image_to_art(
+            &image(4, &[BLACK, BLACK, WHITE, WHITE].repeat(3)),
+            &ImageArtOptions {
+                edge_threshold: Some(0.5),
+                ..options()
+            },
+        );
+        assert_eq!(art.lines, [" ||@"; 3]);
+    }
+
+    #[test]
+    fn markup_groups_colour_runs() {
+        let art = ImageArt {
+            lines: vec!["[@ @".into(), " ".into()],
+            colors: vec![
+                vec![
+                    Some([0xff, 0, 0]),
+                    Some([0xff, 0, 0]),
+                    None,
+                    Some([0, 0, 0xff]),
+                ],
+                vec![None],
+            ],
+        };
+        assert_eq!(art.markup(), "[color=#ff0000][[@ [/][color=#0000ff]@[/]\n ");
+
+        let spans = parse_markup(&art.markup());
+        assert_eq!(spans[0].text, "[@ ");
+        assert_eq!(spans[0].style.color.as_deref(), Some("#ff0000"));
+        assert_eq!(spans[1].text, "@");
+    }
+}
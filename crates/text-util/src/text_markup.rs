@@ -0,0 +1,114 @@
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkupStyle {
+    pub color: Option<String>,
+    pub bold: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupSpan {
+    pub text: String,
+    pub style: MarkupStyle,
+}
+
+#[derive(Debug, Clone)]
+enum Tag {
+    Color(String),
+    Bold,
+}
+
+impl Tag {
+    fn parse(tag: &str) -> Option<Self> {
+        match tag.split_once('=') {
+            Some(("color", color)) if !color.is_empty() => Some(Self::Color(color.to_string())),
+            None if tag == "b" => Some(Self::Bold),
+            _ => None,
+        }
+    }
+    fn name(&self) -> &str {
+        match self {
+            Self::Color(_) => "color",
+            Self::Bold => "b",
+        }
+    }
+}
+
+/// Parses inline markup such as `[color=primary]Hello[/] [b]world[/b]`.
+///
+/// `[/]` closes the innermost tag and `[/name]` the innermost tag with that name.
+/// `[[` is a literal `[`, unknown tags are kept as text.
+pub fn parse_markup(markup: &str) -> Vec<MarkupSpan> {
+    let mut spans = Vec::new();
+    let mut tags: Vec<Tag> = Vec::new();
+    let mut style = MarkupStyle::default();
+    let mut text = String::new();
+
+    let mut rest = markup;
+    while let Some(start) = rest.find('[') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(escaped) = rest.strip_prefix("[[") {
+            text.push('[');
+            rest = escaped;
+            continue;
+        }
+
+        let Some(end) = rest.find(']') else {
+            break;
+        };
+        let tag = &rest[1..end];
+
+        let changed = if let Some(name) = tag.strip_prefix('/') {
+            tags.iter()
+                .rposition(|tag| name.is_empty() || tag.name() == name)
+                .map(|index| tags.remove(index))
+                .is_some()
+        } else if let Some(tag) = Tag::parse(tag) {
+            tags.push(tag);
+            true
+        } else {
+            false
+        };
+
+        if changed {
+            push_span(&mut spans, &mut text, &style);
+            style = tags_style(&tags);
+        } else {
+            text.push_str(&rest[..=end]);
+        }
+        rest = &rest[end + 1..];
+    }
+    text.push_str(rest);
+    push_span(&mut spans, &mut text, &style);
+
+    spans
+}
+
+/// The text of `markup` without tags.
+pub fn strip_markup(markup: &str) -> String {
+    parse_markup(markup)
+        .into_iter()
+        .map(|span| span.text)
+        .collect()
+}
+
+fn tags_style(tags: &[Tag]) -> MarkupStyle {
+    let mut style = MarkupStyle::default();
+    for tag in tags {
+        match tag {
+            Tag::Color(color) => style.color = Some(color.clone()),
+            Tag::Bold => style.bold = true,
+        }
+    }
+    style
+}
+
+fn push_span(spans: &mut Vec<MarkupSpan>, text: &mut String, style: &MarkupStyle) {
+    if text.is_empty() {
+        return;
+    }
+    spans.push(MarkupSpan {
+        text: std::mem::take(text),
+        style: style.clone(),
+    });
+}
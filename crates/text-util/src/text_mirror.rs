@@ -1,5 +1,8 @@
 use hashbrown::HashMap;
-pub(crate) fn mirror_lines_with(lines: &[String], map: HashMap<char, char>) -> Vec<String> {
+
+pub type MirrorChars = HashMap<char, char>;
+
+pub fn mirror_lines_with(lines: &[String], map: &HashMap<char, char>) -> Vec<String> {
     let mut mirrored_lines: Vec<String> = lines.to_vec();
 
     // Reverse
@@ -16,15 +19,22 @@ pub(crate) fn mirror_lines_with(lines: &[String], map: HashMap<char, char>) -> V
 }
 
 pub fn mirror_lines(lines: &[String]) -> Vec<String> {
-    let map: HashMap<char, char> = HashMap::from_iter(
-        MIRROR_CHARS
-            .into_iter()
-            .chain(MIRROR_CHARS.into_iter().map(|(a, b)| (b, a))),
-    );
-    mirror_lines_with(lines, map)
+    mirror_lines_with(lines, &mirror_map())
+}
+
+pub fn mirror_map() -> MirrorChars {
+    mirror_map_from(MIRROR_CHARS)
+}
+
+// Builds a map that mirrors each pair in both directions.
+pub fn mirror_map_from(pairs: impl IntoIterator<Item = (char, char)>) -> MirrorChars {
+    pairs
+        .into_iter()
+        .flat_map(|(a, b)| [(a, b), (b, a)])
+        .collect()
 }
 
-const MIRROR_CHARS: [(char, char); 8] = [
+const MIRROR_CHARS: [(char, char); 24] = [
     ('\\', '/'),
     ('´', '`'),
     ('<', '>'),
@@ -33,4 +43,31 @@ const MIRROR_CHARS: [(char, char); 8] = [
     ('[', ']'),
     ('(', ')'),
     ('{', '}'),
+    ('⌐', '¬'),
+    ('«', '»'),
+    ('┌', '┐'),
+    ('└', '┘'),
+    ('├', '┤'),
+    ('╔', '╗'),
+    ('╚', '╝'),
+    ('╠', '╣'),
+    ('╭', '╮'),
+    ('╰', '╯'),
+    ('▌', '▐'),
+    ('◄', '►'),
+    ('←', '→'),
+    ('◀', '▶'),
+    ('↖', '↗'),
+    ('↙', '↘'),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_mirror_both_ways() {
+        let lines = ["⌐«┌╔◄←".to_string(), "→►╗┐»¬".to_string()];
+        assert_eq!(mirror_lines(&lines), ["→►╗┐»¬", "⌐«┌╔◄←"]);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::text_width::{line_cells, lines_width};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    Clockwise,
+    Half,
+    CounterClockwise,
+}
+
+// Rotating requires every character to have a rotated counterpart, returns `None` otherwise.
+pub fn rotate_char(c: char, rotation: Rotation) -> Option<char> {
+    if SYMMETRIC_CHARS.contains(&c) {
+        return Some(c);
+    }
+
+    let index = ROTATION_CYCLES
+        .iter()
+        .find_map(|cycle| cycle.iter().position(|&x| x == c).map(|i| (cycle, i)));
+    let (cycle, index) = index?;
+    let steps = match rotation {
+        Rotation::Clockwise => 1,
+        Rotation::Half => 2,
+        Rotation::CounterClockwise => 3,
+    };
+    Some(cycle[(index + steps) % 4])
+}
+
+pub fn rotate_lines(lines: &[String], rotation: Rotation) -> Option<Vec<String>> {
+    let height = lines.len();
+    let width = lines_width(lines);
+
+    let grid = lines
+        .iter()
+        .map(|line| {
+            let mut row = line_cells(line)
+                .into_iter()
+                .map(|c| rotate_char(c, rotation))
+                .collect::<Option<Vec<char>>>()?;
+            row.resize(width, ' ');
+            Some(row)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(match rotation {
+        Rotation::Clockwise => (0..width)
+            .map(|x| (0..height).rev().map(|y| grid[y][x]).collect())
+            .collect(),
+        Rotation::Half => grid
+            .iter()
+            .rev()
+            .map(|row| row.iter().rev().collect())
+            .collect(),
+        Rotation::CounterClockwise => (0..width)
+            .rev()
+            .map(|x| (0..height).map(|y| grid[y][x]).collect())
+            .collect(),
+    })
+}
+
+const SYMMETRIC_CHARS: [char; 14] = [
+    ' ', '+', '#', '*', 'o', 'O', 'x', 'X', '@', '·', '┼', '░', '▒', '▓',
+];
+
+// Each cycle lists a character followed by its successive clockwise rotations.
+const ROTATION_CYCLES: [[char; 4]; 8] = [
+    ['-', '|', '-', '|'],
+    ['/', '\\', '/', '\\'],
+    ['<', '^', '>', 'v'],
+    ['─', '│', '─', '│'],
+    ['═', '║', '═', '║'],
+    ['┌', '┐', '┘', '└'],
+    ['├', '┬', '┤', '┴'],
+    ['◄', '▲', '►', '▼'],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn quarter_turns_rotate_the_grid_and_characters() {
+        let art = lines(&["-/", "<+"]);
+        assert_eq!(
+            rotate_lines(&art, Rotation::Clockwise),
+            Some(lines(&["^|", "+\\"]))
+        );
+        assert_eq!(
+            rotate_lines(&art, Rotation::CounterClockwise),
+            Some(lines(&["\\+", "|v"]))
+        );
+    }
+
+    #[test]
+    fn half_turns_reverse_rows_and_columns() {
+        assert_eq!(
+            rotate_lines(&lines(&["-/", "<┌"]), Rotation::Half),
+            Some(lines(&["┘>", "/-"]))
+        );
+    }
+
+    #[test]
+    fn unrotatable_characters_fail_the_rotation() {
+        assert_eq!(rotate_char('a', Rotation::Clockwise), None);
+        assert_eq!(rotate_lines(&lines(&["-a"]), Rotation::Half), None);
+        assert_eq!(rotate_lines(&lines(&["a"]), Rotation::Clockwise), None);
+    }
+}
@@ -0,0 +1,129 @@
+use unicode_width::UnicodeWidthChar;
+
+/// Fills the second cell of a double width character.
+pub const WIDE_CHAR_PADDING: char = '\u{200B}';
+
+// Zero width and control characters don't occupy a cell and are dropped.
+pub fn char_width(c: char) -> usize {
+    if c == WIDE_CHAR_PADDING {
+        return 1;
+    }
+    c.width().unwrap_or(0).min(2)
+}
+
+pub fn line_width(line: &str) -> usize {
+    line.chars().map(char_width).sum()
+}
+
+pub fn lines_width(lines: &[String]) -> usize {
+    lines.iter().map(|line| line_width(line)).max().unwrap_or(0)
+}
+
+// Wide characters are followed by `WIDE_CHAR_PADDING` so every entry is one cell.
+pub fn line_cells(line: &str) -> Vec<char> {
+    let mut cells = Vec::with_capacity(line.len());
+    for c in line.chars() {
+        match char_width(c) {
+            0 => {}
+            1 => cells.push(c),
+            _ => cells.extend([c, WIDE_CHAR_PADDING]),
+        }
+    }
+    cells
+}
+
+pub fn cells_to_line(cells: &[char]) -> String {
+    cells.iter().filter(|&&c| c != WIDE_CHAR_PADDING).collect()
+}
+
+/// Marks the cells `is_solid` accepts, padding after a wide character is solid along with it.
+pub fn solid_cells(cells: &[char], is_solid: impl Fn(char) -> bool) -> Box<[bool]> {
+    let mut solid = false;
+    cells
+        .iter()
+        .map(|&c| {
+            if c != WIDE_CHAR_PADDING {
+                solid = is_solid(c);
+            }
+            solid
+        })
+        .collect()
+}
+
+/// Row major cells of `lines`, ragged lines are padded with spaces to the widest line.
+pub fn lines_to_cells(lines: &[String]) -> (usize, usize, Vec<char>) {
+    let width = lines_width(lines);
+    let mut data = Vec::with_capacity(width * lines.len());
+    for line in lines {
+        let mut cells = line_cells(line);
+        cells.resize(width, ' ');
+        data.extend(cells);
+    }
+    (width, lines.len(), data)
+}
+
+/// Takes `width` cells starting at cell `start`, padding with spaces past the end of the line.
+///
+/// Wide characters cut by either edge are replaced with spaces.
+pub fn slice_cells(line: &str, start: usize, width: usize) -> String {
+    let mut cells = line_cells(line);
+    if start > 0 && cells.get(start) == Some(&WIDE_CHAR_PADDING) {
+        cells[start] = ' ';
+    }
+    let end = start + width;
+    if end < cells.len() && cells[end] == WIDE_CHAR_PADDING {
+        cells[end - 1] = ' ';
+    }
+    cells.resize(cells.len().max(end), ' ');
+    cells_to_line(&cells[start..end])
+}
+
+pub fn pad_lines(lines: &[String]) -> Vec<String> {
+    let width = lines_width(lines);
+    lines
+        .iter()
+        .map(|line| {
+            let padding = width - line_width(line);
+            format!("{}{}", line, " ".repeat(padding))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_characters_take_two_cells() {
+        assert_eq!(char_width('a'), 1);
+        assert_eq!(char_width('漢'), 2);
+        assert_eq!(char_width('\u{301}'), 0);
+        assert_eq!(line_width("a漢字"), 5);
+        assert_eq!(line_cells("a漢"), ['a', '漢', WIDE_CHAR_PADDING]);
+        assert_eq!(cells_to_line(&line_cells("a漢")), "a漢");
+    }
+
+    #[test]
+    fn slices_blank_cut_wide_characters() {
+        assert_eq!(slice_cells("a漢b", 1, 2), "漢");
+        assert_eq!(slice_cells("a漢b", 2, 2), " b");
+        assert_eq!(slice_cells("a漢b", 0, 2), "a ");
+        assert_eq!(slice_cells("a漢b", 3, 3), "b  ");
+    }
+
+    #[test]
+    fn padding_is_solid_with_its_wide_character() {
+        let cells = line_cells("漢 a");
+        assert_eq!(
+            *solid_cells(&cells, |c| !c.is_whitespace()),
+            [true, true, false, true]
+        );
+    }
+
+    #[test]
+    fn lines_pad_to_the_widest_line() {
+        let lines = ["a".to_string(), "漢字".to_string(), String::new()];
+        assert_eq!(pad_lines(&lines), ["a   ", "漢字", "    "]);
+        assert_eq!(lines_to_cells(&lines[..2]), (4, 2, line_cells("a   漢字")));
+    }
+}
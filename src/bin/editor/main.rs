@@ -30,7 +30,7 @@ fn main() {
         FontAtlasPlugin,
         TilesetPlugin,
         TilemapPlugin,
-        PhysicsPlugin,
+        PhysicsPlugin::default(),
         GlyphTexturePlugin,
         GlyphRenderPlugin,
         DebugPlugin,
@@ -12,6 +12,7 @@ use bevy::{
 use bevy_ascii_game::{
     debug::DebugPlugin,
     debug_menu::plugin::DebugMenuPlugin,
+    glyph_collision::glyph_collision_shape,
     mount::{horse::spawn::create_horse, HorsePlugin},
     physics_grids::{
         parallax::ParallaxLayer, GamePhysicsGrid, GamePhysicsGridMarker, PhysicsGridPlugin,
@@ -38,9 +39,16 @@ use glyph_render::{
     glyph_render_plugin::{GlyphRenderPlugin, SolidColor, GlyphTexture, GlyphTextureSource},
     glyph_sprite::{GlyphSprite, GlyphTexturePlugin},
 };
-use grid_physics::{collision::Aabb, plugin::PhysicsPlugin, solid::SolidPhysicsBundle};
+use grid_physics::{
+    collision::{Aabb, Collider},
+    one_way::OneWay,
+    plugin::PhysicsPlugin,
+    solid::SolidPhysicsBundle,
+};
 use rand_core::RngCore;
-use spatial_grid::{depth::Depth, position::SpatialBundle, PositionPropagationPlugin};
+use spatial_grid::{
+    depth::Depth, direction::Direction, position::SpatialBundle, PositionPropagationPlugin,
+};
 use std::sync::Arc;
 
 fn main() {
@@ -56,7 +64,11 @@ fn main() {
                 }),
                 ..Default::default()
             }),
-        (PhysicsPlugin, PhysicsGridPlugin, PositionPropagationPlugin),
+        (
+            PhysicsPlugin::default(),
+            PhysicsGridPlugin,
+            PositionPropagationPlugin,
+        ),
         (PlayerPlugin, HorsePlugin),
         (
             GlyphTexturePlugin,
@@ -144,11 +156,11 @@ fn late_setup_system(
             }
         }
 
-        let texture = GlyphTexture::new(Arc::new(GlyphTextureSource {
-            data: data.into(),
+        let texture = GlyphTexture::new(Arc::new(GlyphTextureSource::new(
             width,
             height,
-        }));
+            data.into(),
+        )));
         commands.spawn((
             GlyphSprite {
                 texture: glyph_textures.add(texture),
@@ -181,11 +193,11 @@ fn late_setup_system(
             }
         }
 
-        let texture = GlyphTexture::new(Arc::new(GlyphTextureSource {
-            data: data.into(),
+        let texture = GlyphTexture::new(Arc::new(GlyphTextureSource::new(
             width,
             height,
-        }));
+            data.into(),
+        )));
 
         commands.spawn((
             GlyphSprite {
@@ -222,27 +234,26 @@ fn late_setup_system(
         .flatten()
         .collect();
 
-        let texture = GlyphTexture::new(Arc::new(GlyphTextureSource {
-            data,
-            width,
-            height,
-        }));
+        let texture = Arc::new(GlyphTextureSource::new(width, height, data));
 
         commands.spawn((
             GlyphSprite {
-                texture: glyph_textures.add(texture),
-                offset: IVec2::new(15, 20),
+                texture: glyph_textures.add(GlyphTexture::new(texture.clone())),
+                offset: IVec2::ZERO,
+            },
+            SolidPhysicsBundle {
+                position: IVec2::new(15, 20).into(),
+                collider: Collider {
+                    shape: glyph_collision_shape(&texture),
+                    ..Default::default()
+                },
+                ..Default::default()
             },
-            SpatialBundle::default(),
             SolidColor {
                 color: Hsva::new(0., 0.0, 0.8, 1.).into(),
             },
             GamePhysicsGridMarker,
             Depth(-100.0),
-            ParallaxLayer {
-                factor: 1.0,
-                target: grid.unwrap(),
-            },
         ));
     }
 }
@@ -262,6 +273,7 @@ fn setup_system(
             position: IVec2::new(20, 8).into(),
             ..Default::default()
         },
+        OneWay(Direction::PosY),
         GamePhysicsGridMarker,
         Depth(-5.0),
     ));
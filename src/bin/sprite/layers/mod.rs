@@ -89,11 +89,7 @@ impl EditorLayer {
             })
             .map(|(pos, tile)| {
                 (
-                    GlyphTextureSource {
-                        data: tile.data.clone(),
-                        width: TILE_USIZE,
-                        height: TILE_USIZE,
-                    },
+                    GlyphTextureSource::new(TILE_USIZE, TILE_USIZE, tile.data.clone()),
                     Position(*pos * TILE_DIMENSIONS),
                 )
             })
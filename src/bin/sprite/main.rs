@@ -59,7 +59,7 @@ fn main() {
         ),
         FontAtlasPlugin,
         (TilesetPlugin, TilemapPlugin),
-        PhysicsPlugin,
+        PhysicsPlugin::default(),
         TilesetPanelPlugin,
         PhysicsGridPlugin,
         DebugPlugin,
@@ -54,7 +54,11 @@ fn main() {
             GlyphAnimationGraphPlugin,
         ),
         (TilesetPlugin, TilemapPlugin),
-        (PositionPropagationPlugin, PhysicsPlugin, PhysicsGridPlugin),
+        (
+            PositionPropagationPlugin,
+            PhysicsPlugin::default(),
+            PhysicsGridPlugin,
+        ),
         (TilesetPanelPlugin, DebugPlugin, UiSectionsPlugin),
     ))
     .add_systems(Startup, setup_system)
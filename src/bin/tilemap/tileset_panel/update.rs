@@ -19,7 +19,7 @@ use bevy_ascii_game::{
         asset::TilemapSource,
         chunk::TilemapChunk,
         component::Tilemap,
-        loader::ChunkSettings,
+        loader::{chunk_file_name, ChunkSettings},
         saver::{ChunkSaver, TilemapSaver},
     },
     tileset::asset::TilesetSource,
@@ -149,6 +149,7 @@ pub(super) fn save_tilemap_system(
     };
 
     let server = server.clone();
+    let chunk_compression = tilemap.chunk_compression;
 
     for (chunk_id, chunk) in tilemap.chunk_handles.iter() {
         let (chunk_id, chunk) = (*chunk_id, chunks.get(chunk.id()).unwrap().clone());
@@ -158,8 +159,10 @@ pub(super) fn save_tilemap_system(
         IoTaskPool::get()
             .spawn(async move {
                 let asset_source = server.get_source(AssetSourceId::default()).unwrap();
-                let chunk_label =
-                    format!("tilemaps/output/{}_{}.chunk.bin", chunk_id.x, chunk_id.y);
+                let chunk_label = format!(
+                    "tilemaps/output/{}",
+                    chunk_file_name(chunk_id, chunk_compression)
+                );
 
                 let output = asset_source
                     .writer()
@@ -168,7 +171,14 @@ pub(super) fn save_tilemap_system(
                 let mut output = output.await.unwrap();
 
                 ChunkSaver
-                    .save(&mut output, &erased, &ChunkSettings::default())
+                    .save(
+                        &mut output,
+                        &erased,
+                        &ChunkSettings {
+                            size: None,
+                            compression: Some(chunk_compression),
+                        },
+                    )
                     .await
                     .unwrap();
             })
@@ -0,0 +1,60 @@
+use bevy::math::UVec2;
+use glyph_render::glyph_render_plugin::GlyphTextureSource;
+use grid_physics::collision::CompositeCollisionShape;
+use text_util::text_width::solid_cells;
+
+/// Merges the cells of `texture` that draw something into collision rectangles,
+/// relative to the bottom left cell like `GlyphSprite::offset`.
+pub fn glyph_collision_shape(texture: &GlyphTextureSource) -> CompositeCollisionShape {
+    let cells = solid_cells(&texture.data, |c| !c.is_whitespace());
+    let (width, height) = (texture.width, texture.height);
+
+    // Texture rows are stored from the top.
+    CompositeCollisionShape::from_cells(UVec2::new(width as u32, height as u32), |cell| {
+        cells[cell.x as usize + (height - 1 - cell.y as usize) * width]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::IVec2;
+    use text_util::text_width::lines_to_cells;
+
+    use super::*;
+
+    fn rects(lines: &[&str]) -> Vec<(IVec2, UVec2)> {
+        let lines = lines
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>();
+        let (width, height, data) = lines_to_cells(&lines);
+        let texture = GlyphTextureSource::new(width, height, data.into());
+        glyph_collision_shape(&texture)
+            .iter_at(IVec2::ZERO)
+            .map(|aabb| (aabb.start, aabb.size))
+            .collect()
+    }
+
+    #[test]
+    fn l_shape_merges_into_two_rects() {
+        assert_eq!(
+            rects(&["#  ", "#  ", "###"]),
+            [
+                (IVec2::ZERO, UVec2::new(3, 1)),
+                (IVec2::new(0, 1), UVec2::new(1, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn bottom_row_is_y_zero() {
+        assert_eq!(rects(&["  ", "  ", " #"]), [(IVec2::new(1, 0), UVec2::ONE)]);
+        assert_eq!(rects(&["# ", "  "]), [(IVec2::new(0, 1), UVec2::ONE)]);
+    }
+
+    #[test]
+    fn wide_characters_are_solid_in_both_cells() {
+        assert_eq!(rects(&["a日 "]), [(IVec2::ZERO, UVec2::new(3, 1))]);
+        assert_eq!(rects(&[" 日"]), [(IVec2::new(1, 0), UVec2::new(2, 1))]);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod debug;
 pub mod debug_menu;
+pub mod glyph_collision;
 pub mod mount;
 pub mod physics_grids;
 pub mod player;
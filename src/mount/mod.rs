@@ -1,11 +1,12 @@
 use bevy::{color::palettes::css, prelude::*};
 use glyph_render::{
+    glyph_animation::anchors::GlyphAnimationAnchors,
     glyph_animation_graph::player::GlyphAnimationGraphTarget,
-    glyph_render_plugin::{SolidColor, GlyphSpriteMirrored},
+    glyph_render_plugin::{GlyphSpriteMirrored, SolidColor},
 };
 use grid_physics::{
-    free::FreeMarker, gravity::Gravity, movement::Movement, plugin::PhysicsUpdateSet,
-    velocity::Velocity,
+    collision::Collider, free::FreeMarker, gravity::Gravity, movement::Movement,
+    plugin::PhysicsUpdateSet, sets::physics_systems_enabled, velocity::Velocity,
 };
 use spatial_grid::position::Position;
 
@@ -31,11 +32,12 @@ impl Plugin for HorsePlugin {
                 dismount_system,
             ),
         )
+        // Riders follow the mount each tick so both are interpolated alike.
         .add_systems(
-            PostUpdate,
+            FixedUpdate,
             update_rider_system
-                .in_set(PhysicsUpdateSet::PostUpdate)
-                .after(PhysicsUpdateSet::Update),
+                .run_if(physics_systems_enabled)
+                .after(PhysicsUpdateSet::PostUpdate),
         )
         .add_systems(
             PreUpdate,
@@ -129,18 +131,37 @@ fn dismount_system(
     }
 }
 
+type MountQueryData<'a> = (
+    &'a Position,
+    &'a Velocity,
+    &'a MountOrigin,
+    &'a MountRider,
+    Option<&'a GlyphAnimationAnchors>,
+    Has<GlyphSpriteMirrored>,
+);
+
 fn update_rider_system(
-    q_mount: Query<
-        (&Position, &Velocity, &MountOrigin, &MountRider),
-        (With<MountMarker>, Without<PlayerMarker>),
+    q_mount: Query<MountQueryData, (With<MountMarker>, Without<PlayerMarker>)>,
+    mut q_player: Query<
+        (&mut Position, &mut Velocity, &Collider),
+        (Without<MountMarker>, With<PlayerMarker>),
     >,
-    mut q_player: Query<(&mut Position, &mut Velocity), (Without<MountMarker>, With<PlayerMarker>)>,
 ) {
-    for (mount_pos, mount_vel, mount_origin, &MountRider { rider }) in q_mount.iter() {
-        let Ok((mut rider_pos, mut rider_vel)) = q_player.get_mut(rider) else {
+    for (mount_pos, mount_vel, mount_origin, &MountRider { rider }, anchors, mirrored) in
+        q_mount.iter()
+    {
+        let Ok((mut rider_pos, mut rider_vel, rider_collider)) = q_player.get_mut(rider) else {
             continue;
         };
-        **rider_pos = mount_pos.0 + mount_origin.origin;
+        // The saddle anchor marks the rider's first column, which becomes its last
+        // column once the mount's frame is mirrored.
+        let rider_width = rider_collider.aabb().map_or(1, |aabb| aabb.size.x as i32);
+        let origin = match anchors.and_then(|anchors| anchors.get("saddle")) {
+            Some(&saddle) if mirrored => saddle - IVec2::X * (rider_width - 1),
+            Some(&saddle) => saddle,
+            None => mount_origin.origin,
+        };
+        **rider_pos = mount_pos.0 + origin;
         **rider_vel = **mount_vel;
     }
 }
@@ -7,11 +7,12 @@ use glyph_render::{
     font::{CustomFont, FontSize},
     glyph_buffer::{GlyphBuffer, TargetGlyphBuffer},
 };
-use grid_physics::{collision::Collider, plugin::PhysicsUpdateSet, velocity::Velocity};
+use grid_physics::{collision::Collider, interpolation::PreviousPosition, velocity::Velocity};
 use parallax::parallax_system;
 use spatial_grid::{
     grid::{PhysicsGridMember, SpatialGrid},
     position::{Position, SpatialBundle},
+    remainder::Remainder,
 };
 
 pub mod parallax;
@@ -105,9 +106,18 @@ fn create_physics_grids(
     );
 }
 
+type TrackedPlayerQueryData<'a> = (
+    &'a Position,
+    &'a Remainder,
+    Option<&'a PreviousPosition>,
+    &'a Velocity,
+    &'a Collider,
+);
+
+// Follows the player where it's drawn, between physics ticks.
 pub(crate) fn grid_translate(
     q_player: Query<
-        (&Position, &Velocity, &Collider),
+        TrackedPlayerQueryData,
         (With<PlayerMarker>, Without<PrimaryGlyphBufferMarker>),
     >,
     mut q_primary_buffer: Query<
@@ -117,14 +127,20 @@ pub(crate) fn grid_translate(
     mut prediction_offset_current: Local<IVec2>,
     mut prediction_offset_target: Local<Vec2>,
     time: Res<Time>,
+    fixed_time: Res<Time<Fixed>>,
 ) {
     let Ok((mut position, &GlyphBuffer { textures: _, size })) = q_primary_buffer.get_single_mut()
     else {
         return;
     };
-    let Ok((player_pos, player_velocity, collider)) = q_player.get_single() else {
+    let Ok((player_pos, player_remainder, previous, player_velocity, collider)) =
+        q_player.get_single()
+    else {
         return;
     };
+    let player_pos = previous.map_or(**player_pos, |previous| {
+        previous.interpolate(player_pos, player_remainder, fixed_time.overstep_fraction())
+    });
     let Some(aabb) = collider.aabb() else { return };
 
     let x_padding = size.x as i32 / 4;
@@ -149,7 +165,7 @@ pub(crate) fn grid_translate(
         prediction_offset_current.y += difference.y.round() as i32;
     }
 
-    let predicted_position = **player_pos - **position + *prediction_offset_current;
+    let predicted_position = player_pos - **position + *prediction_offset_current;
     let predicted_center = predicted_position + aabb.start + aabb.size.as_ivec2();
 
     let lower_screen_pos = predicted_position;
@@ -178,7 +194,6 @@ impl Plugin for PhysicsGridPlugin {
                 PostUpdate,
                 (grid_translate, parallax_system)
                     .chain()
-                    .after(PhysicsUpdateSet::PostUpdate)
                     .before(TransformSystem::TransformPropagate),
             )
             .add_systems(Startup, create_physics_grids)
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use glyph_render::glyph_animation::hitboxes::{update_animation_hitboxes, GlyphAnimationHitboxes};
+use grid_physics::collision::{Aabb, Collider, CompositeCollisionShape};
+
+/// Replaces the entity's `Collider` with the frame's hurt boxes whenever it declares any,
+/// restoring the original shape on frames that don't.
+#[derive(Debug, Component, Clone, Default)]
+pub struct HurtboxCollider {
+    base: Option<CompositeCollisionShape>,
+}
+
+pub struct HurtboxPlugin;
+impl Plugin for HurtboxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_hurtbox_colliders.after(update_animation_hitboxes),
+        );
+    }
+}
+
+fn update_hurtbox_colliders(
+    mut q_colliders: Query<
+        (&GlyphAnimationHitboxes, &mut Collider, &mut HurtboxCollider),
+        Changed<GlyphAnimationHitboxes>,
+    >,
+) {
+    for (hitboxes, mut collider, mut hurtbox) in q_colliders.iter_mut() {
+        let base = hurtbox.base.get_or_insert_with(|| collider.shape.clone());
+        collider.shape = if hitboxes.hurtboxes.is_empty() {
+            base.clone()
+        } else {
+            CompositeCollisionShape {
+                shapes: hitboxes
+                    .hurtboxes
+                    .iter()
+                    .map(|rect| Aabb {
+                        start: rect.min,
+                        size: rect.size().as_uvec2(),
+                    })
+                    .collect(),
+            }
+        };
+    }
+}
@@ -7,9 +7,11 @@ use self::{
     reset::player_reset_system,
 };
 use grid_physics::actor::ActorPhysicsBundle;
+use hurtbox::HurtboxPlugin;
 use interaction::{InteractionSource, PlayerInteractionPlugin};
 
 pub(crate) mod animation;
+pub mod hurtbox;
 pub mod input;
 pub mod interaction;
 pub(crate) mod movement;
@@ -26,6 +28,7 @@ impl Plugin for PlayerPlugin {
             PlayerInputPlugin,
             PlayerMovementPlugin,
             PlayerInteractionPlugin,
+            HurtboxPlugin,
         ))
         .add_systems(Update, (player_reset_system, set_animation_target));
     }
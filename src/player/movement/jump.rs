@@ -1,8 +1,10 @@
 use bevy::prelude::*;
 
-use crate::player::input::player_inputs::JumpMarker;
+use crate::player::input::player_inputs::{self, JumpMarker};
 use grid_physics::{
     free::{FreeGrounded, FreeMarker},
+    movement::MovementObstructed,
+    one_way::{DropThrough, OneWay},
     velocity::Velocity,
 };
 
@@ -22,7 +24,13 @@ impl Default for PlayerJumpVelocity {
 pub(crate) fn player_jump_system(
     mut commands: Commands,
     mut q_player: Query<
-        (Entity, &mut Velocity, &PlayerJumpVelocity),
+        (
+            Entity,
+            &mut Velocity,
+            &PlayerJumpVelocity,
+            &player_inputs::Movement,
+            Option<&MovementObstructed>,
+        ),
         (
             MovementFilter,
             With<JumpMarker>,
@@ -30,8 +38,18 @@ pub(crate) fn player_jump_system(
             With<FreeMarker>,
         ),
     >,
+    q_one_way: Query<(), With<OneWay>>,
 ) {
-    for (entity, mut velocity, jump_velocity) in q_player.iter_mut() {
+    for (entity, mut velocity, jump_velocity, input, obstructed) in q_player.iter_mut() {
+        // Jumping while holding down drops through one-way platforms.
+        let on_one_way = obstructed
+            .and_then(|obstructed| obstructed.neg_y)
+            .is_some_and(|solid| q_one_way.contains(solid));
+        if input.vertical < -0.5 && on_one_way {
+            commands.entity(entity).insert(DropThrough::default());
+            continue;
+        }
+
         velocity.y = jump_velocity.velocity;
 
         commands.entity(entity).insert(FreeMarker);
@@ -10,7 +10,7 @@ use self::{
     walk::{player_walk_system, PlayerWalkSpeed},
 };
 use super::PlayerMarker;
-use grid_physics::sets::physics_systems_enabled;
+use grid_physics::{plugin::PhysicsUpdateSet, sets::physics_systems_enabled};
 
 pub(crate) mod direction;
 pub(crate) mod jump;
@@ -20,8 +20,9 @@ pub(crate) mod walk;
 pub(crate) struct PlayerMovementPlugin;
 impl Plugin for PlayerMovementPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
+        // Runs with physics so lunges and jumps don't depend on the frame rate.
         app.add_systems(
-            Update,
+            FixedUpdate,
             ((
                 player_walk_system,
                 player_jump_system,
@@ -32,7 +33,8 @@ impl Plugin for PlayerMovementPlugin {
             )
                 .chain()
                 .run_if(physics_systems_enabled),)
-                .chain(),
+                .chain()
+                .before(PhysicsUpdateSet::PostUpdate),
         );
     }
 }
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use super::{
+    hurtbox::HurtboxCollider,
     input::{controller::PlayerInputController, player_inputs::ResetMarker},
     movement::{walk::PlayerWalkSpeed, PlayerMovementBundle},
     PlayerBundle,
@@ -10,8 +11,8 @@ use glyph_render::{
     glyph_animation_graph::bundle::GlyphAnimationGraphBundle, glyph_buffer::TargetGlyphBuffer,
 };
 use grid_physics::{
-    actor::ActorPhysicsBundle,
-    collision::{Aabb, Collider},
+    actor::{ActorPhysicsBundle, StepHeight},
+    collision::Aabb,
     free::FreeMarker,
     gravity::Gravity,
     velocity::Velocity,
@@ -59,13 +60,11 @@ pub fn create_player<'a>(
                     position: IVec2::new(10, 10).into(),
                     ..Default::default()
                 },
-                collider: Collider {
-                    shape: Aabb {
-                        start: IVec2::ZERO,
-                        size: UVec2 { x: 6, y: 5 },
-                    }
-                    .into(),
-                },
+                collider: Aabb {
+                    start: IVec2::ZERO,
+                    size: UVec2 { x: 6, y: 5 },
+                }
+                .into(),
 
                 ..Default::default()
             },
@@ -76,8 +75,10 @@ pub fn create_player<'a>(
             ..Default::default()
         },
         FreeMarker,
+        StepHeight { up: 1, down: 1 },
         Gravity::default(),
         Velocity::default(),
         Depth(0.0),
+        HurtboxCollider::default(),
     ))
 }
@@ -7,6 +7,7 @@ use bevy::{
 };
 
 use crate::tileset::asset::TilesetSource;
+use text_util::compression::Compression;
 
 use super::chunk::{TilemapChunk, EMPTY_TILE};
 
@@ -17,6 +18,7 @@ pub struct TilemapSource {
     pub(crate) tileset_names: HashMap<String, usize>,
     pub(crate) tilesets: Vec<Handle<TilesetSource>>,
     pub chunk_handles: HashMap<IVec2, Handle<TilemapChunk>>,
+    pub chunk_compression: Compression,
 }
 
 impl TilemapSource {
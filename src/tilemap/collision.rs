@@ -0,0 +1,307 @@
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+use grid_physics::{
+    collision::{Aabb, Collider, CompositeCollisionShape},
+    solid::Solid,
+};
+
+use super::{
+    asset::TilemapSource,
+    chunk::{TilemapChunk, EMPTY_TILE},
+    component::Tilemap,
+};
+use crate::tileset::asset::TilesetSource;
+
+/// Merged collision rectangles of each chunk, relative to the tilemap.
+#[derive(Debug, Component, Default)]
+pub struct TilemapChunkColliders(HashMap<IVec2, Vec<Aabb>>);
+
+/// Tilemap, tileset and chunk assets along with their change events.
+#[derive(SystemParam)]
+pub(crate) struct TilemapColliderAssets<'w, 's> {
+    ev_tilemaps: EventReader<'w, 's, AssetEvent<TilemapSource>>,
+    ev_tilesets: EventReader<'w, 's, AssetEvent<TilesetSource>>,
+    ev_chunks: EventReader<'w, 's, AssetEvent<TilemapChunk>>,
+    tilemaps: Res<'w, Assets<TilemapSource>>,
+    tilesets: Res<'w, Assets<TilesetSource>>,
+    chunks: Res<'w, Assets<TilemapChunk>>,
+}
+
+type TilemapColliderQueryData<'a> = (
+    Entity,
+    Ref<'a, Tilemap>,
+    Option<&'a mut TilemapChunkColliders>,
+    Option<&'a Collider>,
+);
+
+pub(crate) fn update_tilemap_colliders(
+    mut commands: Commands,
+    mut q_tilemaps: Query<TilemapColliderQueryData, With<Solid>>,
+    mut assets: TilemapColliderAssets,
+) {
+    let TilemapColliderAssets {
+        ev_tilemaps,
+        ev_tilesets,
+        ev_chunks,
+        tilemaps,
+        tilesets,
+        chunks,
+    } = &mut assets;
+
+    let changed_tilemaps = ev_tilemaps
+        .read()
+        .filter_map(changed_asset)
+        .collect::<Vec<_>>();
+    let tilesets_changed = ev_tilesets.read().filter_map(changed_asset).count() > 0;
+    let changed_chunks = ev_chunks
+        .read()
+        .filter_map(changed_asset)
+        .collect::<Vec<_>>();
+
+    for (entity, tilemap, chunk_colliders, collider) in q_tilemaps.iter_mut() {
+        let Some(tilemap_source) = tilemaps.get(tilemap.id()) else {
+            continue;
+        };
+        let rebuild_all = chunk_colliders.is_none()
+            || tilemap.is_changed()
+            || tilesets_changed
+            || changed_tilemaps.contains(&tilemap.id());
+
+        let mut inserted = None;
+        let chunk_colliders = match chunk_colliders {
+            Some(chunk_colliders) => chunk_colliders.into_inner(),
+            None => inserted.insert(TilemapChunkColliders::default()),
+        };
+
+        let mut changed = rebuild_all;
+        chunk_colliders
+            .0
+            .retain(|chunk_id, _| tilemap_source.chunk_handles.contains_key(chunk_id));
+
+        for (chunk_id, chunk) in tilemap_source.chunk_handles.iter() {
+            if !rebuild_all
+                && chunk_colliders.0.contains_key(chunk_id)
+                && !changed_chunks.contains(&chunk.id())
+            {
+                continue;
+            }
+            let aabbs = chunks
+                .get(chunk.id())
+                .map(|chunk_data| chunk_aabbs(tilemap_source, *chunk_id, chunk_data, tilesets))
+                .unwrap_or_default();
+            chunk_colliders.0.insert(*chunk_id, aabbs);
+            changed = true;
+        }
+
+        if changed {
+            commands.entity(entity).insert(Collider {
+                shape: CompositeCollisionShape {
+                    shapes: chunk_colliders.0.values().flatten().cloned().collect(),
+                },
+                layers: collider.map(|collider| collider.layers).unwrap_or_default(),
+            });
+        }
+        if let Some(chunk_colliders) = inserted {
+            commands.entity(entity).insert(chunk_colliders);
+        }
+    }
+}
+
+fn changed_asset<A: Asset>(event: &AssetEvent<A>) -> Option<AssetId<A>> {
+    match event {
+        AssetEvent::Added { id }
+        | AssetEvent::Modified { id }
+        | AssetEvent::LoadedWithDependencies { id }
+        | AssetEvent::Removed { id } => Some(*id),
+        AssetEvent::Unused { .. } => None,
+    }
+}
+
+// Tiles are placed the same way `extract_tilemaps` draws them, parts of tiles
+// larger than the tilemap's tile size are cut off at the chunk's edge.
+fn chunk_aabbs(
+    tilemap: &TilemapSource,
+    chunk_id: IVec2,
+    chunk: &TilemapChunk,
+    tilesets: &Assets<TilesetSource>,
+) -> Vec<Aabb> {
+    let size = tilemap.chunk_size * tilemap.tile_size;
+    let mut solid = vec![false; (size.x * size.y) as usize];
+
+    for (index, tile) in chunk.data.iter().enumerate() {
+        if tile == &EMPTY_TILE {
+            continue;
+        }
+        let Some(tileset) = tilesets.get(tilemap.tilesets[tile.0 as usize].id()) else {
+            continue;
+        };
+        let Some(Some(cells)) = tileset.collision.get(tile.1 as usize) else {
+            continue;
+        };
+        let tile_offset = UVec2::new(
+            (index as u32) % tilemap.chunk_size.x,
+            (index as u32) / tilemap.chunk_size.x,
+        ) * tileset.tile_size;
+
+        let tile_size = tileset.tile_size;
+        for y in 0..tile_size.y {
+            for x in 0..tile_size.x {
+                let cell = tile_offset + UVec2::new(x, y);
+                // Tile rows are stored from the top.
+                if cell.cmplt(size).all()
+                    && cells[(x + (tile_size.y - 1 - y) * tile_size.x) as usize]
+                {
+                    solid[(cell.x + cell.y * size.x) as usize] = true;
+                }
+            }
+        }
+    }
+
+    let chunk_position = chunk_id * size.as_ivec2();
+    CompositeCollisionShape::from_cells(size, |cell| solid[(cell.x + cell.y * size.x) as usize])
+        .iter_at(chunk_position)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use grid_physics::collision::CollisionLayers;
+
+    use super::*;
+
+    fn tileset(tile_size: UVec2, collision: Vec<Option<Box<[bool]>>>) -> TilesetSource {
+        TilesetSource {
+            display_name: String::new(),
+            id: String::new(),
+            tile_size,
+            _tile_ids: default(),
+            _tile_labels: default(),
+            tiles: vec![],
+            collision,
+        }
+    }
+
+    fn tilemap(
+        chunk_size: UVec2,
+        tile_size: UVec2,
+        tileset: Handle<TilesetSource>,
+    ) -> TilemapSource {
+        TilemapSource {
+            chunk_size,
+            tile_size,
+            tileset_names: default(),
+            tilesets: vec![tileset],
+            chunk_handles: default(),
+            chunk_compression: default(),
+        }
+    }
+
+    fn rects(aabbs: impl IntoIterator<Item = Aabb>) -> Vec<(IVec2, UVec2)> {
+        let mut rects = aabbs
+            .into_iter()
+            .map(|aabb| (aabb.start, aabb.size))
+            .collect::<Vec<_>>();
+        rects.sort_by_key(|(start, _)| (start.y, start.x));
+        rects
+    }
+
+    #[test]
+    fn chunk_aabbs_merge_solid_cells_at_the_chunk_position() {
+        let mut tilesets = Assets::<TilesetSource>::default();
+        // The second tile is only solid in its top row.
+        let handle = tilesets.add(tileset(
+            UVec2::splat(2),
+            vec![
+                Some(vec![true; 4].into()),
+                Some(vec![true, true, false, false].into()),
+                None,
+            ],
+        ));
+        let tilemap = tilemap(UVec2::new(3, 1), UVec2::splat(2), handle);
+        let chunk = TilemapChunk {
+            data: vec![(0, 0), (0, 1), (0, 2)].into(),
+        };
+
+        assert_eq!(
+            rects(chunk_aabbs(&tilemap, IVec2::new(1, -1), &chunk, &tilesets)),
+            [
+                (IVec2::new(6, -2), UVec2::new(2, 2)),
+                (IVec2::new(8, -1), UVec2::new(2, 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn chunk_aabbs_cut_large_tiles_at_the_chunk_edge() {
+        let mut tilesets = Assets::<TilesetSource>::default();
+        let handle = tilesets.add(tileset(UVec2::new(3, 1), vec![Some(vec![true; 3].into())]));
+        let tilemap = tilemap(UVec2::ONE, UVec2::new(2, 1), handle);
+        let chunk = TilemapChunk {
+            data: vec![(0, 0)].into(),
+        };
+
+        assert_eq!(
+            rects(chunk_aabbs(&tilemap, IVec2::ZERO, &chunk, &tilesets)),
+            [(IVec2::ZERO, UVec2::new(2, 1))]
+        );
+    }
+
+    #[test]
+    fn tilemap_colliders_follow_modified_chunks() {
+        let mut app = App::new();
+        app.add_event::<AssetEvent<TilemapSource>>()
+            .add_event::<AssetEvent<TilesetSource>>()
+            .add_event::<AssetEvent<TilemapChunk>>()
+            .init_resource::<Assets<TilemapSource>>()
+            .init_resource::<Assets<TilesetSource>>()
+            .init_resource::<Assets<TilemapChunk>>()
+            .add_systems(Update, update_tilemap_colliders);
+
+        let world = app.world_mut();
+        let tileset = world
+            .resource_mut::<Assets<TilesetSource>>()
+            .add(tileset(UVec2::ONE, vec![Some(vec![true].into())]));
+        let chunk = world
+            .resource_mut::<Assets<TilemapChunk>>()
+            .add(TilemapChunk {
+                data: vec![(0, 0), EMPTY_TILE].into(),
+            });
+        let mut source = tilemap(UVec2::new(2, 1), UVec2::ONE, tileset);
+        source.chunk_handles.insert(IVec2::ZERO, chunk.clone());
+        let source = world.resource_mut::<Assets<TilemapSource>>().add(source);
+        let entity = world
+            .spawn((
+                Tilemap(source),
+                Solid,
+                Collider {
+                    layers: CollisionLayers {
+                        member: 2,
+                        filter: 2,
+                    },
+                    ..default()
+                },
+            ))
+            .id();
+
+        app.update();
+        let collider = app.world().get::<Collider>(entity).unwrap();
+        assert_eq!(
+            rects(collider.shape.iter_at(IVec2::ZERO)),
+            [(IVec2::ZERO, UVec2::ONE)]
+        );
+        assert_eq!(collider.layers.member, 2);
+
+        let world = app.world_mut();
+        world
+            .resource_mut::<Assets<TilemapChunk>>()
+            .get_mut(chunk.id())
+            .unwrap()
+            .data[1] = (0, 0);
+        world.send_event(AssetEvent::Modified { id: chunk.id() });
+        app.update();
+        let collider = app.world().get::<Collider>(entity).unwrap();
+        assert_eq!(
+            rects(collider.shape.iter_at(IVec2::ZERO)),
+            [(IVec2::ZERO, UVec2::new(2, 1))]
+        );
+    }
+}
@@ -9,6 +9,7 @@ use super::meta::TilemapMeta;
 use super::{asset::TilemapSource, chunk::TilemapChunk};
 use crate::tileset::asset::TilesetSource;
 use std::path::PathBuf;
+use text_util::compression::Compression;
 
 #[derive(Default)]
 pub struct TilemapLoader {}
@@ -19,7 +20,7 @@ impl AssetLoader for TilemapLoader {
     type Settings = ();
 
     fn extensions(&self) -> &[&str] {
-        &["tilemap.ron"]
+        &["tilemap.ron", "tilemap.ron.gz", "tilemap.ron.zst"]
     }
 
     fn load(
@@ -31,6 +32,7 @@ impl AssetLoader for TilemapLoader {
         Box::pin(async move {
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
+            let bytes = Compression::from_path(load_context.path()).decompress(bytes)?;
             let meta = ron::de::from_bytes::<TilemapMeta>(&bytes)?;
 
             let mut tilesets = Vec::new();
@@ -51,14 +53,15 @@ impl AssetLoader for TilemapLoader {
 
             for pos in meta.chunks.iter() {
                 let pos: IVec2 = (*pos).into();
-                let path =
-                    PathBuf::from(&meta.chunk_dir).join(format!("{}_{}.chunk.bin", pos.x, pos.y));
+                let path = PathBuf::from(&meta.chunk_dir)
+                    .join(chunk_file_name(pos, meta.chunk_compression));
 
                 let chunk = ChunkLoader
                     .load(
-                        &mut VecReader::new(load_context.read_asset_bytes(path).await.unwrap()),
+                        &mut VecReader::new(load_context.read_asset_bytes(path).await?),
                         &ChunkSettings {
                             size: Some(meta.chunk_size.into()),
+                            compression: Some(meta.chunk_compression),
                         },
                         load_context,
                     )
@@ -83,14 +86,21 @@ impl AssetLoader for TilemapLoader {
                 tileset_names,
                 tilesets: tileset_handles,
                 chunk_handles,
+                chunk_compression: meta.chunk_compression,
             })
         })
     }
 }
 
+pub fn chunk_file_name(pos: IVec2, compression: Compression) -> String {
+    compression.file_name(&format!("{}_{}.chunk.bin", pos.x, pos.y))
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Default, Clone)]
 pub struct ChunkSettings {
-    size: Option<UVec2>,
+    pub size: Option<UVec2>,
+    // Taken from the file extension when not set.
+    pub compression: Option<Compression>,
 }
 
 #[derive(Default)]
@@ -101,17 +111,21 @@ impl AssetLoader for ChunkLoader {
     type Settings = ChunkSettings;
 
     fn extensions(&self) -> &[&str] {
-        &["chunk.bin"]
+        &["chunk.bin", "chunk.bin.gz", "chunk.bin.zst"]
     }
     fn load(
         &self,
         reader: &mut dyn Reader,
         settings: &Self::Settings,
-        _load_context: &mut LoadContext,
+        load_context: &mut LoadContext,
     ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
         Box::pin(async move {
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
+            let bytes = settings
+                .compression
+                .unwrap_or_else(|| Compression::from_path(load_context.path()))
+                .decompress(bytes)?;
 
             let data = bytes
                 .chunks_exact(8)
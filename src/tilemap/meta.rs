@@ -1,4 +1,5 @@
 use bevy::{asset::Asset, prelude::Deref, reflect::TypePath};
+use text_util::compression::Compression;
 
 #[derive(serde::Deserialize, serde::Serialize, Asset, TypePath, Clone)]
 pub(crate) struct TilemapMeta {
@@ -6,6 +7,8 @@ pub(crate) struct TilemapMeta {
     pub(crate) tile_size: (u32, u32),
     pub(crate) tilesets: Vec<String>,
     pub(crate) chunk_dir: String,
+    #[serde(default)]
+    pub(crate) chunk_compression: Compression,
     pub(crate) chunks: Vec<(i32, i32)>,
 }
 
@@ -1,5 +1,6 @@
 pub mod asset;
 pub mod chunk;
+pub mod collision;
 pub mod component;
 pub(crate) mod extract;
 pub mod loader;
@@ -3,9 +3,12 @@ use bevy::{
     render::{ExtractSchedule, RenderApp},
 };
 
+use grid_physics::plugin::PhysicsUpdateSet;
+
 use super::{
     asset::TilemapSource,
     chunk::TilemapChunk,
+    collision::update_tilemap_colliders,
     extract::extract_tilemaps,
     loader::{ChunkLoader, TilemapLoader},
 };
@@ -16,7 +19,11 @@ impl Plugin for TilemapPlugin {
         app.init_asset::<TilemapSource>()
             .init_asset::<TilemapChunk>()
             .init_asset_loader::<TilemapLoader>()
-            .init_asset_loader::<ChunkLoader>();
+            .init_asset_loader::<ChunkLoader>()
+            .add_systems(
+                FixedUpdate,
+                update_tilemap_colliders.before(PhysicsUpdateSet::PostUpdate),
+            );
 
         app.get_sub_app_mut(RenderApp)
             .unwrap()
@@ -42,6 +42,7 @@ impl AssetSaver for TilemapSaver {
                 chunk_size: asset.chunk_size.into(),
                 tile_size: asset.tile_size.into(),
                 chunk_dir: "tilemaps/output".into(),
+                chunk_compression: asset.chunk_compression,
                 tilesets: asset
                     .tilesets
                     .iter()
@@ -83,6 +84,7 @@ impl AssetSaver for ChunkSaver {
                     tileset.to_le_bytes().into_iter().chain(tile.to_le_bytes())
                 })
                 .collect::<Box<[_]>>();
+            let data = settings.compression.unwrap_or_default().compress(&data)?;
 
             dbg!(writer.write_all(&data).await).unwrap();
             writer.flush().await.unwrap();
@@ -11,4 +11,7 @@ pub struct TilesetSource {
     pub(crate) _tile_ids: HashMap<String, usize>,
     pub(crate) _tile_labels: Vec<String>,
     pub tiles: Vec<Arc<GlyphTextureSource>>,
+    /// Solid cells of each tile, rows from top to bottom like the tile's texture.
+    /// `None` for tiles without any solid cells.
+    pub(crate) collision: Vec<Option<Box<[bool]>>>,
 }
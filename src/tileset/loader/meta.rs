@@ -5,16 +5,56 @@ pub(crate) struct TilesetMeta {
     pub(crate) display_name: String,
     pub(crate) id: String,
     pub(crate) size: (u32, u32),
+    #[serde(default)]
+    pub(crate) mirror_map: Option<String>,
+    #[serde(default)]
+    pub(crate) collision: TileCollisionMeta,
     pub(crate) assets: Vec<TileSourceMeta>,
 }
 
+#[derive(serde::Deserialize, Asset, TypePath, Clone, Default)]
+pub(crate) struct TileCollisionMeta {
+    /// Labels of tiles that collide as drawn, a label also matches every tile it prefixes,
+    /// so `"0"` covers all tiles of `All("0")` and `"0-1-0"` its mirrored and rotated variants.
+    #[serde(default)]
+    pub(crate) tiles: Vec<String>,
+    /// Characters that are solid in every tile.
+    #[serde(default)]
+    pub(crate) characters: String,
+}
+
 #[derive(serde::Deserialize, Asset, TypePath, Clone)]
 pub(crate) struct TileSourceMeta {
     pub(crate) asset: String,
     pub(crate) tiles: AssetTiles,
+    /// Flipped and rotated copies stored right after each tile and its mirrored copy,
+    /// so tiles appended later don't move the indices saved chunks refer to.
+    #[serde(default)]
+    pub(crate) variants: Vec<TileVariant>,
 }
 
 #[derive(serde::Deserialize, Asset, TypePath, Clone)]
 pub(crate) enum AssetTiles {
     All(String),
 }
+
+#[derive(serde::Deserialize, Clone, Copy, Debug)]
+pub(crate) enum TileVariant {
+    Flip,
+    MirrorFlip,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl TileVariant {
+    pub(crate) fn suffix(self) -> &'static str {
+        match self {
+            Self::Flip => "f",
+            Self::MirrorFlip => "mf",
+            Self::Rotate90 => "r90",
+            Self::Rotate180 => "r180",
+            Self::Rotate270 => "r270",
+        }
+    }
+}
@@ -1,41 +1,38 @@
 pub(crate) mod meta;
 
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 use bevy::{
     asset::{io::Reader, AssetLoader, LoadContext},
+    log::warn,
     math::UVec2,
     utils::{
         hashbrown::{HashMap, HashSet},
         ConditionalSendFuture,
     },
 };
-use glyph_render::glyph_render_plugin::GlyphTextureSource;
+use glyph_render::{glyph_render_plugin::GlyphTextureSource, mirror_map::MirrorMap};
+use text_util::{
+    compression::Compression,
+    text_art::parse_art_lines,
+    text_flip::flip_lines,
+    text_rotate::{rotate_lines, Rotation},
+    text_width::{line_width, slice_cells, solid_cells},
+};
 
-use self::meta::TilesetMeta;
+use self::meta::{TileCollisionMeta, TileVariant, TilesetMeta};
 
 use super::asset::TilesetSource;
 
 #[derive(Default)]
 pub(crate) struct TilesetLoader {}
-impl TilesetLoader {
-    fn parse_bytes(bytes: Vec<u8>) -> Vec<String> {
-        bytes
-            .split(|&b| b == b'\n')
-            .map(|line| {
-                String::from_utf8(line.strip_suffix(b"\r").unwrap_or(line).to_vec()).unwrap()
-            })
-            .collect::<Vec<_>>()
-    }
-}
-
 impl AssetLoader for TilesetLoader {
     type Asset = TilesetSource;
     type Error = anyhow::Error;
     type Settings = ();
 
     fn extensions(&self) -> &[&str] {
-        &["tileset.ron"]
+        &["tileset.ron", "tileset.ron.gz", "tileset.ron.zst"]
     }
 
     fn load(
@@ -47,6 +44,7 @@ impl AssetLoader for TilesetLoader {
         Box::pin(async move {
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
+            let bytes = Compression::from_path(load_context.path()).decompress(bytes)?;
             let meta = ron::de::from_bytes::<TilesetMeta>(&bytes)?;
             let unique_assets: HashSet<String> = meta
                 .assets
@@ -58,61 +56,31 @@ impl AssetLoader for TilesetLoader {
             for asset_path in unique_assets.iter() {
                 artwork_data.insert(
                     asset_path,
-                    Self::parse_bytes(load_context.read_asset_bytes(asset_path).await.unwrap()),
+                    parse_art_lines(
+                        &Compression::from_path(Path::new(asset_path))
+                            .decompress(load_context.read_asset_bytes(asset_path).await?)?,
+                    )?,
                 );
             }
 
+            let mirror_map = MirrorMap::load(load_context, meta.mirror_map.as_ref()).await?;
+
             let tile_size: UVec2 = meta.size.into();
-            let mut tiles = vec![];
             let mut tile_ids = HashMap::new();
             let mut tile_labels = vec![];
-
-            for asset in meta.assets.iter() {
-                match &asset.tiles {
-                    meta::AssetTiles::All(name) => {
-                        let source_data = artwork_data.get(&asset.asset).unwrap();
-                        let tiles_y = source_data.len().div_euclid(tile_size.y as usize);
-
-                        for tile_y in 0..tiles_y {
-                            let mut tile_x = 0;
-                            'add_x: loop {
-                                let mut tile = Vec::new();
-                                for dy in 0..tile_size.y as usize {
-                                    let y = tile_y * tile_size.y as usize + dy;
-
-                                    let x_start = tile_x * tile_size.x as usize;
-                                    let x_end = x_start + tile_size.x as usize;
-                                    if x_end > source_data[y].chars().count() {
-                                        break 'add_x;
-                                    }
-
-                                    tile.push(
-                                        source_data[y]
-                                            .chars()
-                                            .skip(x_start)
-                                            .take(x_end - x_start)
-                                            .collect::<String>(),
-                                    );
-                                }
-
-                                let label = format!("{}-{}-{}", name, tile_x, tile_y);
-                                tile_ids.insert(label.clone(), tiles.len());
-                                tile_labels.push(label);
-                                tiles.push(Arc::new(GlyphTextureSource::from(&tile)));
-
-                                let mirrored_label = format!("{}-{}-{}-m", name, tile_x, tile_y);
-                                let mirrored_data = text_util::text_mirror::mirror_lines(&tile);
-                                tile_ids.insert(mirrored_label.clone(), tiles.len());
-                                tile_labels.push(mirrored_label);
-                                tiles.push(Arc::new(GlyphTextureSource::from(&mirrored_data)));
-
-                                tile_x += 1;
-                            }
-                        }
-                    }
-                }
+            let mut tiles = vec![];
+            for (label, data) in build_tiles(&meta, &artwork_data, &mirror_map) {
+                tile_ids.insert(label.clone(), tiles.len());
+                tile_labels.push(label);
+                tiles.push(Arc::new(GlyphTextureSource::from(&data)));
             }
 
+            let collision = tile_labels
+                .iter()
+                .zip(tiles.iter())
+                .map(|(label, tile)| tile_collision(&meta.collision, label, tile))
+                .collect();
+
             Ok(TilesetSource {
                 display_name: meta.display_name,
                 id: meta.id,
@@ -120,7 +88,141 @@ impl AssetLoader for TilesetLoader {
                 _tile_ids: tile_ids,
                 _tile_labels: tile_labels,
                 tiles,
+                collision,
             })
         })
     }
 }
+
+/// Labels and art of every tile in index order. Each tile is followed by its mirrored copy
+/// and the variants its asset opts into, so saved chunk indices survive appended tiles.
+fn build_tiles(
+    meta: &TilesetMeta,
+    artwork_data: &HashMap<&String, Vec<String>>,
+    mirror_map: &MirrorMap,
+) -> Vec<(String, Vec<String>)> {
+    let tile_size: UVec2 = meta.size.into();
+    let mut tiles = vec![];
+
+    for asset in meta.assets.iter() {
+        match &asset.tiles {
+            meta::AssetTiles::All(name) => {
+                let source_data = artwork_data.get(&asset.asset).unwrap();
+                let tiles_y = source_data.len().div_euclid(tile_size.y as usize);
+
+                for tile_y in 0..tiles_y {
+                    let mut tile_x = 0;
+                    'add_x: loop {
+                        let mut tile = Vec::new();
+                        for dy in 0..tile_size.y as usize {
+                            let y = tile_y * tile_size.y as usize + dy;
+
+                            let x_start = tile_x * tile_size.x as usize;
+                            let x_end = x_start + tile_size.x as usize;
+                            if x_end > line_width(&source_data[y]) {
+                                break 'add_x;
+                            }
+
+                            tile.push(slice_cells(&source_data[y], x_start, tile_size.x as usize));
+                        }
+
+                        let label = format!("{}-{}-{}", name, tile_x, tile_y);
+                        tiles.push((label.clone(), tile.clone()));
+                        tiles.push((format!("{}-m", label), mirror_map.mirror_lines(&tile)));
+                        for &variant in asset.variants.iter() {
+                            tiles.push((
+                                format!("{}-{}", label, variant.suffix()),
+                                tile_variant(&tile, variant, tile_size, mirror_map, &label),
+                            ));
+                        }
+                        tile_x += 1;
+                    }
+                }
+            }
+        }
+    }
+    tiles
+}
+
+fn tile_variant(
+    tile: &[String],
+    variant: TileVariant,
+    tile_size: UVec2,
+    mirror_map: &MirrorMap,
+    label: &str,
+) -> Vec<String> {
+    let rotation = match variant {
+        TileVariant::Flip => return flip_lines(tile),
+        TileVariant::MirrorFlip => return flip_lines(&mirror_map.mirror_lines(tile)),
+        TileVariant::Rotate90 => Rotation::Clockwise,
+        TileVariant::Rotate180 => Rotation::Half,
+        TileVariant::Rotate270 => Rotation::CounterClockwise,
+    };
+    let rotated = (rotation == Rotation::Half || tile_size.x == tile_size.y)
+        .then(|| rotate_lines(tile, rotation))
+        .flatten();
+    // Keeps the variant's index even when the art can't be rotated.
+    rotated.unwrap_or_else(|| {
+        warn!("Tile {} can't be rotated, using it unrotated", label);
+        tile.to_vec()
+    })
+}
+
+fn tile_collision(
+    meta: &TileCollisionMeta,
+    label: &str,
+    tile: &GlyphTextureSource,
+) -> Option<Box<[bool]>> {
+    let whole_tile = meta.tiles.iter().any(|solid| {
+        label
+            .strip_prefix(solid.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+    });
+
+    let cells = solid_cells(&tile.data, |c| {
+        (whole_tile && !c.is_whitespace()) || meta.characters.contains(c)
+    });
+
+    cells.contains(&true).then_some(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(meta: &str, art: &str) -> Vec<String> {
+        let meta = ron::de::from_str::<TilesetMeta>(meta).unwrap();
+        let path = "tiles.art".to_string();
+        let artwork_data = HashMap::from([(&path, art.lines().map(String::from).collect())]);
+        build_tiles(&meta, &artwork_data, &MirrorMap::default())
+            .into_iter()
+            .map(|(label, _)| label)
+            .collect()
+    }
+
+    #[test]
+    fn variants_are_opt_in() {
+        let meta = r#"(display_name: "", id: "", size: (1, 1), assets: [
+            (asset: "tiles.art", tiles: All("a")),
+        ])"#;
+        assert_eq!(labels(meta, "/-"), ["a-0-0", "a-0-0-m", "a-1-0", "a-1-0-m"]);
+    }
+
+    #[test]
+    fn appended_tiles_keep_existing_indices() {
+        let meta = r#"(display_name: "", id: "", size: (1, 1), assets: [
+            (asset: "tiles.art", tiles: All("a"), variants: [Flip, Rotate90]),
+        ])"#;
+        let before = labels(meta, "/-\n|.");
+        let after = labels(meta, "/-\n|.\n+*");
+        assert_eq!(before.len(), 16);
+        assert_eq!(after[..before.len()], before);
+
+        let meta = r#"(display_name: "", id: "", size: (1, 1), assets: [
+            (asset: "tiles.art", tiles: All("a"), variants: [Flip, Rotate90]),
+            (asset: "tiles.art", tiles: All("b")),
+        ])"#;
+        let after = labels(meta, "/-\n|.");
+        assert_eq!(after[..before.len()], before);
+    }
+}